@@ -0,0 +1,191 @@
+// src/baseline.rs
+// =============================================================================
+// This module remembers known failures so only new ones fail the run.
+//
+// A legacy repository may have hundreds of broken links that can't all be
+// fixed at once. Failing every build on them makes link-guardian useless as
+// a gate. With a baseline:
+//
+//   link-guardian github https://github.com/org/repo --baseline links-baseline.json
+//
+// - The first run (no file yet) records every failing link and passes
+// - Later runs only fail on failing links that are NOT in the baseline
+// - Known failures are still reported, as warnings instead of failures
+// - Links in the baseline that no longer fail are reported as fixed;
+//   --update-baseline removes them, so the baseline only ever shrinks
+//
+// A link counts as failing when --fail-on / --warn-on say it fails the run.
+// Links are matched by their normalized URL (see cache::cache_key), so a
+// known-broken link that now times out, or is written with a #fragment, is
+// still known.
+//
+// Rust concepts:
+// - BTreeMap: Sorted keys give a stable file that diffs nicely in git
+// - Option<Baseline>: "no baseline file yet" is a normal case, not an error
+// =============================================================================
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::cache::cache_key;
+use crate::checker::LinkCheckResult;
+
+// Bumped whenever the file format changes
+const FORMAT_VERSION: u32 = 1;
+
+// The failures that were accepted when the baseline was recorded
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    version: u32,
+    /// Normalized URL -> status name when it was recorded ("broken", ...)
+    failures: BTreeMap<String, String>,
+}
+
+// How the current failures compare to the baseline
+#[derive(Debug)]
+pub struct Comparison<'a> {
+    /// Failing now, but not in the baseline - these fail the run
+    pub new: Vec<&'a LinkCheckResult>,
+    /// Failing now and already in the baseline
+    pub known: usize,
+    /// In the baseline, but no longer failing (fixed or removed)
+    pub fixed: Vec<String>,
+}
+
+impl Baseline {
+    // A baseline holding the given failures
+    pub fn from_failures(failures: &[&LinkCheckResult]) -> Self {
+        Baseline {
+            version: FORMAT_VERSION,
+            failures: failures
+                .iter()
+                .map(|r| (cache_key(&r.url), r.status.name().to_string()))
+                .collect(),
+        }
+    }
+
+    // Reads a baseline file; Ok(None) if it doesn't exist yet
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Could not read baseline {}", path.display()))
+            }
+        };
+
+        let baseline: Baseline = serde_json::from_str(&text)
+            .with_context(|| format!("{} is not a link-guardian baseline", path.display()))?;
+        if baseline.version != FORMAT_VERSION {
+            anyhow::bail!(
+                "Baseline {} has version {}, expected {}",
+                path.display(),
+                baseline.version,
+                FORMAT_VERSION
+            );
+        }
+        Ok(Some(baseline))
+    }
+
+    // Writes the baseline as pretty JSON, one failure per line
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json + "\n")
+            .with_context(|| format!("Could not write baseline {}", path.display()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.failures.len()
+    }
+
+    // The normalized URLs of the known failures (for FailurePolicy)
+    pub fn urls(&self) -> HashSet<String> {
+        self.failures.keys().cloned().collect()
+    }
+
+    // Splits the current failures into new and known ones, and finds the
+    // baseline entries that no longer fail
+    pub fn compare<'a>(&self, failures: &[&'a LinkCheckResult]) -> Comparison<'a> {
+        let new: Vec<&LinkCheckResult> = failures
+            .iter()
+            .copied()
+            .filter(|r| !self.failures.contains_key(&cache_key(&r.url)))
+            .collect();
+
+        let failing: HashSet<String> = failures.iter().map(|r| cache_key(&r.url)).collect();
+        let fixed = self
+            .failures
+            .keys()
+            .filter(|url| !failing.contains(*url))
+            .cloned()
+            .collect();
+
+        Comparison {
+            known: failures.len() - new.len(),
+            new,
+            fixed,
+        }
+    }
+
+    // Drops entries that no longer fail (--update-baseline)
+    pub fn remove(&mut self, urls: &[String]) {
+        for url in urls {
+            self.failures.remove(url);
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why does --update-baseline only remove entries?
+//    - A baseline is a ratchet: it may shrink as links get fixed, but a new
+//      broken link should never slip in silently
+//    - To accept new failures on purpose, delete the file and record again
+//
+// 2. What is the 'a in Comparison<'a>?
+//    - A lifetime: `new` borrows results owned by the caller
+//    - It tells Rust the Comparison can't outlive those results
+//
+// 3. Why anyhow::bail!?
+//    - bail!(...) is a shortcut for return Err(anyhow!(...))
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::LinkStatus;
+
+    #[test]
+    fn test_compare() {
+        let broken = |url: &str| LinkCheckResult::new(url, LinkStatus::Broken { code: 404 }, None, Vec::new());
+        let (a, b, c) = (broken("https://a.com"), broken("https://b.com/x"), broken("https://c.com"));
+        let mut baseline = Baseline::from_failures(&[&a, &b]);
+
+        // The same link written differently is still known
+        let b_again = broken("https://B.com/x#section");
+        let comparison = baseline.compare(&[&b_again, &c]);
+        assert_eq!(comparison.new.len(), 1);
+        assert_eq!(comparison.new[0].url, "https://c.com");
+        assert_eq!(comparison.known, 1);
+        assert_eq!(comparison.fixed, vec!["https://a.com/".to_string()]);
+
+        baseline.remove(&comparison.fixed);
+        assert_eq!(baseline.len(), 1);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("link-guardian-baseline-{}.json", std::process::id()));
+        assert_eq!(Baseline::load(&path).unwrap(), None);
+
+        let broken = LinkCheckResult::new("https://a.com", LinkStatus::Broken { code: 404 }, None, Vec::new());
+        let baseline = Baseline::from_failures(&[&broken]);
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), Some(baseline));
+        fs::remove_file(path).unwrap();
+    }
+}
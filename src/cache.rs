@@ -0,0 +1,781 @@
+// src/cache.rs
+// =============================================================================
+// This module remembers link check results between runs.
+//
+// A repository that links to the same 3,000 external pages checks all of
+// them on every push, which is slow and gets us rate-limited. With --cache,
+// results are stored in a JSON file and reused while they are fresh:
+//
+//   link-guardian github https://github.com/org/repo --cache
+//
+// How long a result stays fresh depends on its status. By default "ok" and
+// "redirect" results are reused for 24 hours and failures are never cached,
+// so a broken link is always checked again. --cache-ttl changes this:
+//
+//   --cache-ttl ok=7d --cache-ttl forbidden=1h --cache-ttl redirect=0
+//
+// Entries are keyed by the normalized URL, so "HTTPS://Example.com:443/a#top"
+// and "https://example.com/a" share one entry. Each entry also keeps the HTTP
+// status code it was classified from: when --accept / --accept-for would now
+// decide differently about that code (a 403 that was accepted, or wasn't),
+// the entry is ignored and the link checked again.
+//
+// When a cached "ok" result has expired, it isn't thrown away: if the server
+// sent an ETag or Last-Modified header, the next check asks "has this
+// changed?" (If-None-Match / If-Modified-Since). A 304 Not Modified answer
+// confirms the old result without downloading anything. The crawler does the
+// same for pages, keeping a copy of each page's HTML next to the cache file,
+// so unchanged pages aren't downloaded again either.
+//
+// The file lives in the platform's cache directory (~/.cache/link-guardian
+// on Linux) unless --cache-file says otherwise. `link-guardian cache show`,
+// `cache prune` and `cache clear` inspect and clean it up.
+//
+// Rust concepts:
+// - Arc<Mutex<T>>: Sharing one mutable value between many concurrent checks
+// - BTreeMap: A map that keeps its keys sorted (stable file contents)
+// - SystemTime: Wall-clock timestamps that survive a restart
+// =============================================================================
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::checker::{AcceptPolicy, LinkCheckResult, LinkStatus};
+
+// Bumped whenever the file format changes; older files are ignored
+const FORMAT_VERSION: u32 = 1;
+
+// How long `cache prune` keeps results and pages that have an ETag or
+// Last-Modified, even after their TTL: they still save a download through a
+// conditional request
+const REVALIDATE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+// How long results with one status are reused, e.g. "ok=24h"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusTtl {
+    pub status: String,
+    pub ttl: Duration,
+}
+
+impl FromStr for StatusTtl {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (status, ttl) = text
+            .split_once('=')
+            .ok_or_else(|| format!("expected STATUS=DURATION, got '{}'", text))?;
+        let status = status.trim().to_lowercase();
+        if !LinkStatus::NAMES.contains(&status.as_str()) {
+            return Err(format!(
+                "unknown status '{}' (expected one of {})",
+                status,
+                LinkStatus::NAMES.join(", ")
+            ));
+        }
+        Ok(StatusTtl {
+            status,
+            ttl: parse_duration(ttl)?,
+        })
+    }
+}
+
+// Parses a duration like "30s", "15m", "24h" or "7d" ("0" = never cache)
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    if text == "0" {
+        return Ok(Duration::ZERO);
+    }
+
+    let not_a_duration = || format!("'{}' is not a duration like 30m, 24h or 7d", text);
+
+    let unit_start = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (number, unit) = text.split_at(unit_start);
+    let number: u64 = number.parse().map_err(|_| not_a_duration())?;
+    let unit_seconds: u64 = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(not_a_duration()),
+    };
+    // Absurdly large numbers would overflow u64 seconds
+    let seconds = number.checked_mul(unit_seconds).ok_or_else(not_a_duration)?;
+    Ok(Duration::from_secs(seconds))
+}
+
+// Formats an age in seconds for people, using the two largest units
+//
+// Example: 93784 -> "1d 2h"
+pub fn format_age(seconds: u64) -> String {
+    let units = [("d", 24 * 60 * 60), ("h", 60 * 60), ("m", 60), ("s", 1)];
+    let parts: Vec<String> = units
+        .iter()
+        .scan(seconds, |left, (name, size)| {
+            let count = *left / size;
+            *left %= size;
+            Some((count, name))
+        })
+        .skip_while(|(count, _)| *count == 0)
+        .take(2)
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, name)| format!("{}{}", count, name))
+        .collect();
+
+    if parts.is_empty() {
+        "0s".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+// The time-to-live for each status
+//
+// Statuses without an entry are never cached, except "ok" and "redirect"
+// which default to 24 hours.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheTtls(Vec<StatusTtl>);
+
+impl CacheTtls {
+    // The defaults, changed by `overrides` (later entries win)
+    pub fn new(overrides: &[StatusTtl]) -> Self {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let mut ttls = vec![
+            StatusTtl { status: "ok".to_string(), ttl: day },
+            StatusTtl { status: "redirect".to_string(), ttl: day },
+        ];
+        ttls.extend_from_slice(overrides);
+        CacheTtls(ttls)
+    }
+
+    // How long a result with this status is reused (zero = not cached)
+    pub fn ttl_for(&self, status: &LinkStatus) -> Duration {
+        self.0
+            .iter()
+            .rev()
+            .find(|entry| entry.status == status.name())
+            .map_or(Duration::ZERO, |entry| entry.ttl)
+    }
+}
+
+impl Default for CacheTtls {
+    fn default() -> Self {
+        CacheTtls::new(&[])
+    }
+}
+
+// What a server told us to identify a version of a resource
+//
+// Sent back on the next request so the server can answer 304 Not Modified.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validators {
+    /// The ETag header, e.g. "\"33a64df5\""
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    /// The Last-Modified header, e.g. "Wed, 21 Oct 2015 07:28:00 GMT"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    // Reads ETag and Last-Modified from a response
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        Validators {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    // Makes a request conditional: the server answers 304 if nothing changed
+    pub fn apply(&self, mut request: RequestBuilder) -> RequestBuilder {
+        if let Some(etag) = &self.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+}
+
+// One cached result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// When the link was checked (or confirmed unchanged), in seconds since 1970
+    pub checked_at: u64,
+    /// The result of that check (without locations, they belong to a scan)
+    pub result: LinkCheckResult,
+    /// The HTTP status code it was classified from, if there was a response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<u16>,
+    /// ETag / Last-Modified of the response, for conditional requests
+    #[serde(default, skip_serializing_if = "Validators::is_empty")]
+    pub validators: Validators,
+}
+
+impl CacheEntry {
+    // Returns true if `accept` still classifies the entry's code the same way
+    //
+    // Example: a 403 cached as ok under --accept 403 doesn't agree with a run
+    // without it.
+    fn agrees_with(&self, url: &str, accept: &AcceptPolicy) -> bool {
+        match self.code {
+            Some(code) => accept.accepts(url, code) == matches!(self.result.status, LinkStatus::Ok),
+            None => true,
+        }
+    }
+}
+
+// A crawled page whose HTML is kept for conditional requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PageEntry {
+    /// When the page was fetched (or confirmed unchanged), in seconds since 1970
+    checked_at: u64,
+    validators: Validators,
+    /// Name of the file holding the HTML, inside the pages directory
+    file: String,
+}
+
+// What the cache file contains
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    entries: BTreeMap<String, CacheEntry>,
+    #[serde(default)]
+    pages: BTreeMap<String, PageEntry>,
+}
+
+// The cache shared by all concurrent checks of a run
+pub type SharedCache = Arc<Mutex<ResultCache>>;
+
+// Results of earlier runs, loaded from and saved to one JSON file
+#[derive(Debug)]
+pub struct ResultCache {
+    path: PathBuf,
+    ttls: CacheTtls,
+    entries: BTreeMap<String, CacheEntry>,
+    pages: BTreeMap<String, PageEntry>,
+    /// Something changed since loading, so save() has to write
+    changed: bool,
+}
+
+impl ResultCache {
+    // Where the cache lives when --cache-file isn't given
+    //
+    // Example (Linux): ~/.cache/link-guardian/results.json
+    pub fn default_path() -> Result<PathBuf> {
+        let dir = dirs::cache_dir()
+            .context("Could not find a cache directory; use --cache-file to pick one")?;
+        Ok(dir.join("link-guardian").join("results.json"))
+    }
+
+    // Loads the cache from `path`
+    //
+    // A missing file is an empty cache. An unreadable or outdated one is
+    // also treated as empty (with a warning) - a cache is only an
+    // optimization, so it should never stop a scan.
+    pub fn load(path: PathBuf, ttls: CacheTtls) -> Result<Self> {
+        let empty = || CacheFile {
+            version: FORMAT_VERSION,
+            entries: BTreeMap::new(),
+            pages: BTreeMap::new(),
+        };
+        let file = match fs::read_to_string(&path) {
+            Ok(text) => match serde_json::from_str::<CacheFile>(&text) {
+                Ok(file) if file.version == FORMAT_VERSION => file,
+                Ok(_) => {
+                    warn!("Ignoring cache {} from another version", path.display());
+                    empty()
+                }
+                Err(e) => {
+                    warn!("Ignoring unreadable cache {}: {}", path.display(), e);
+                    empty()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => empty(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Could not read cache {}", path.display()))
+            }
+        };
+
+        debug!(
+            "Loaded {} cached result(s) and {} page(s) from {}",
+            file.entries.len(),
+            file.pages.len(),
+            path.display()
+        );
+        Ok(ResultCache {
+            path,
+            ttls,
+            entries: file.entries,
+            pages: file.pages,
+            changed: false,
+        })
+    }
+
+    // Wraps the cache so concurrent checks can share it
+    pub fn shared(self) -> SharedCache {
+        Arc::new(Mutex::new(self))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // How many crawled pages are kept for conditional requests
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    // The cached result for `url`, if it is still fresh and `accept` agrees
+    // with how it was classified
+    //
+    // The returned result carries `url` as given (not the normalized key)
+    // and says "(cached)" in its message.
+    pub fn get(&self, url: &str, accept: &AcceptPolicy) -> Option<LinkCheckResult> {
+        self.get_at(url, accept, now())
+    }
+
+    fn get_at(&self, url: &str, accept: &AcceptPolicy, now: u64) -> Option<LinkCheckResult> {
+        let entry = self.entries.get(&cache_key(url))?;
+        if !self.is_fresh(entry, now) || !entry.agrees_with(url, accept) {
+            return None;
+        }
+
+        let mut result = entry.result.clone();
+        result.url = url.to_string();
+        result.message = Some(match result.message {
+            Some(message) => format!("{} (cached)", message),
+            None => "(cached)".to_string(),
+        });
+        Some(result)
+    }
+
+    // ETag / Last-Modified of an expired "ok" result, to ask the server
+    // whether it changed
+    pub fn validators(&self, url: &str, accept: &AcceptPolicy) -> Option<Validators> {
+        let entry = self.entries.get(&cache_key(url))?;
+        let usable = matches!(entry.result.status, LinkStatus::Ok)
+            && entry.agrees_with(url, accept)
+            && !entry.validators.is_empty();
+        usable.then(|| entry.validators.clone())
+    }
+
+    // The server answered 304 Not Modified: the old result still holds
+    //
+    // Starts the entry's time-to-live again and returns the result, saying
+    // "(not modified)" in its message.
+    pub fn revalidate(&mut self, url: &str) -> Option<LinkCheckResult> {
+        let entry = self.entries.get_mut(&cache_key(url))?;
+        entry.checked_at = now();
+        self.changed = true;
+
+        let mut result = entry.result.clone();
+        result.url = url.to_string();
+        result.message = Some(match result.message {
+            Some(message) => format!("{} (not modified)", message),
+            None => "(not modified)".to_string(),
+        });
+        Some(result)
+    }
+
+    // Remembers a fresh result, if its status is cached at all
+    //
+    // `code` is the HTTP status code the result was classified from.
+    pub fn insert(&mut self, result: &LinkCheckResult, code: Option<u16>, validators: Validators) {
+        self.insert_at(result, code, validators, now());
+    }
+
+    fn insert_at(&mut self, result: &LinkCheckResult, code: Option<u16>, validators: Validators, now: u64) {
+        let key = cache_key(&result.url);
+        if self.ttls.ttl_for(&result.status).is_zero() {
+            // A failure replaces an older success, so it isn't reused later
+            self.changed |= self.entries.remove(&key).is_some();
+            return;
+        }
+
+        let mut result = result.clone();
+        result.locations.clear();
+        self.entries.insert(
+            key,
+            CacheEntry {
+                checked_at: now,
+                result,
+                code,
+                validators,
+            },
+        );
+        self.changed = true;
+    }
+
+    // ETag / Last-Modified of a cached copy of a page, if we have one
+    pub fn page_validators(&self, url: &str) -> Option<Validators> {
+        let page = self.pages.get(&cache_key(url))?;
+        self.pages_dir().join(&page.file).exists().then(|| page.validators.clone())
+    }
+
+    // The server answered 304 Not Modified for a page: returns our copy
+    pub fn revalidate_page(&mut self, url: &str) -> Option<String> {
+        let dir = self.pages_dir();
+        let page = self.pages.get_mut(&cache_key(url))?;
+        let html = fs::read_to_string(dir.join(&page.file)).ok()?;
+        page.checked_at = now();
+        self.changed = true;
+        Some(html)
+    }
+
+    // Keeps a copy of a page for the next crawl
+    //
+    // Only pages with an ETag or Last-Modified are kept: without them the
+    // server can't tell us the copy is still current. A copy that can't be
+    // written is skipped with a warning.
+    pub fn insert_page(&mut self, url: &str, validators: Validators, html: &str) {
+        let key = cache_key(url);
+        if validators.is_empty() {
+            self.changed |= self.pages.remove(&key).is_some();
+            return;
+        }
+
+        let dir = self.pages_dir();
+        let file = format!("{:016x}.html", fnv1a(&key));
+        if let Err(e) = fs::create_dir_all(&dir).and_then(|_| fs::write(dir.join(&file), html)) {
+            warn!("Could not cache page {}: {}", url, e);
+            return;
+        }
+
+        let page = PageEntry {
+            checked_at: now(),
+            validators,
+            file,
+        };
+        self.pages.insert(key, page);
+        self.changed = true;
+    }
+
+    // Every entry with its age in seconds and whether it is still fresh
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &CacheEntry, u64, bool)> {
+        let now = now();
+        self.entries.iter().map(move |(url, entry)| {
+            (url.as_str(), entry, now.saturating_sub(entry.checked_at), self.is_fresh(entry, now))
+        })
+    }
+
+    // Removes expired entries, returns how many were removed
+    //
+    // Entries that conditional requests can still use (an "ok" result or a
+    // page copy with an ETag / Last-Modified) are kept for at least
+    // REVALIDATE_MAX_AGE.
+    pub fn prune(&mut self) -> usize {
+        self.prune_at(now())
+    }
+
+    fn prune_at(&mut self, now: u64) -> usize {
+        let before = self.entries.len();
+        let ttls = &self.ttls;
+        self.entries.retain(|_, entry| {
+            let mut max_age = ttls.ttl_for(&entry.result.status);
+            if matches!(entry.result.status, LinkStatus::Ok) && !entry.validators.is_empty() {
+                max_age = max_age.max(REVALIDATE_MAX_AGE);
+            }
+            now.saturating_sub(entry.checked_at) < max_age.as_secs()
+        });
+        let removed = before - self.entries.len();
+
+        // Page copies always have validators
+        let page_ttl = self.ttls.ttl_for(&LinkStatus::Ok).max(REVALIDATE_MAX_AGE).as_secs();
+        let (keep, expired) = std::mem::take(&mut self.pages)
+            .into_iter()
+            .partition(|(_, page)| now.saturating_sub(page.checked_at) < page_ttl);
+        self.pages = keep;
+        let expired: BTreeMap<String, PageEntry> = expired;
+        for page in expired.values() {
+            let _ = fs::remove_file(self.pages_dir().join(&page.file));
+        }
+
+        self.changed |= removed > 0 || !expired.is_empty();
+        removed
+    }
+
+    // Removes every entry and page copy, returns how many results there were
+    pub fn clear(&mut self) -> usize {
+        let removed = self.entries.len();
+        self.entries.clear();
+        self.pages.clear();
+        if let Err(e) = fs::remove_dir_all(self.pages_dir()) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                warn!("Could not remove cached pages: {}", e);
+            }
+        }
+        self.changed = true;
+        removed
+    }
+
+    // Writes the cache back to its file (only if something changed)
+    //
+    // The file is written next to the old one and then renamed over it, so
+    // an interrupted run never leaves half a file behind.
+    pub fn save(&mut self) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Could not create cache directory {}", dir.display()))?;
+        }
+
+        let file = CacheFile {
+            version: FORMAT_VERSION,
+            entries: std::mem::take(&mut self.entries),
+            pages: std::mem::take(&mut self.pages),
+        };
+        let json = serde_json::to_string(&file);
+        self.entries = file.entries;
+        self.pages = file.pages;
+
+        let temp = self.path.with_extension("json.tmp");
+        fs::write(&temp, json?)
+            .and_then(|_| fs::rename(&temp, &self.path))
+            .with_context(|| format!("Could not write cache {}", self.path.display()))?;
+
+        debug!("Saved {} cached result(s) to {}", self.entries.len(), self.path.display());
+        self.changed = false;
+        Ok(())
+    }
+
+    fn is_fresh(&self, entry: &CacheEntry, now: u64) -> bool {
+        now.saturating_sub(entry.checked_at) < self.ttls.ttl_for(&entry.result.status).as_secs()
+    }
+
+    // Where page copies are stored: next to the cache file
+    //
+    // Example: results.json -> results-pages/
+    fn pages_dir(&self) -> PathBuf {
+        let stem = self.path.file_stem().and_then(|s| s.to_str()).unwrap_or("cache");
+        self.path.with_file_name(format!("{}-pages", stem))
+    }
+}
+
+// A 64-bit FNV-1a hash, used to name page files (and SARIF fingerprints)
+//
+// Unlike std's DefaultHasher it is guaranteed to give the same value on
+// every platform and Rust version, so files from earlier runs are found again.
+pub fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// The key a URL is stored under
+//
+// Parsing and printing the URL lowercases the scheme and host and drops a
+// default port; the #fragment is removed because it is never sent to the
+// server. URLs that don't parse are used as they are.
+//
+// Example: "HTTPS://Example.com:443/a#top" -> "https://example.com/a"
+pub fn cache_key(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(mut parsed) => {
+            parsed.set_fragment(None);
+            parsed.to_string()
+        }
+        Err(_) => url.to_string(),
+    }
+}
+
+// Seconds since 1970
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why Arc<Mutex<ResultCache>>?
+//    - Hundreds of checks run at the same time and all read and update
+//      the cache
+//    - Arc lets them share one cache; Mutex makes sure only one of them
+//      changes it at a time
+//    - We never hold the lock across an .await, so checks don't wait on
+//      each other's network requests
+//
+// 2. Why store seconds since 1970 instead of an Instant?
+//    - Instant only makes sense inside one run of the program
+//    - SystemTime can be written to a file and compared tomorrow
+//
+// 3. Why write to a temporary file and rename it?
+//    - rename() replaces the old file in one step
+//    - If we are killed while writing, the old cache is still intact
+//
+// 4. What is a 304 Not Modified?
+//    - We send the ETag we got last time in an If-None-Match header
+//    - If the resource is unchanged the server answers 304 with no body,
+//      which is much cheaper than sending the whole thing again
+//
+// 5. What does std::mem::take do?
+//    - Moves a value out and leaves its Default (an empty map) behind
+//    - Here it lets us serialize the entries without cloning them
+//
+// 6. What is wrapping_mul?
+//    - Normal * panics on overflow in debug builds
+//    - Hash functions rely on overflow "wrapping around", so we ask for it
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("link-guardian-test-{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn test_parse_ttl() {
+        let ttl: StatusTtl = "ok=7d".parse().unwrap();
+        assert_eq!(ttl.ttl, Duration::from_secs(7 * 24 * 60 * 60));
+        assert_eq!("forbidden=0".parse::<StatusTtl>().unwrap().ttl, Duration::ZERO);
+        assert!(parse_duration("300000000000000d").is_err());
+        assert!("ok=soon".parse::<StatusTtl>().is_err());
+        assert!("fine=1h".parse::<StatusTtl>().is_err());
+        assert_eq!(format_age(93784), "1d 2h");
+        assert_eq!(format_age(60), "1m");
+    }
+
+    #[test]
+    fn test_freshness_by_status() {
+        let ttls = CacheTtls::new(&["forbidden=1h".parse().unwrap()]);
+        let mut cache = ResultCache::load(temp_path("fresh.json"), ttls).unwrap();
+        let accept = AcceptPolicy::default();
+
+        let ok = LinkCheckResult::new("https://Example.com/a#top", LinkStatus::Ok, Some("HTTP 200"), Vec::new());
+        let forbidden = LinkStatus::Forbidden { code: 403 };
+        let forbidden = LinkCheckResult::new("https://example.com/f", forbidden, None, Vec::new());
+        let gone = LinkCheckResult::new("https://example.com/gone", LinkStatus::Broken { code: 404 }, None, Vec::new());
+        cache.insert_at(&ok, Some(200), Validators::default(), 1000);
+        cache.insert_at(&forbidden, Some(403), Validators::default(), 1000);
+        cache.insert_at(&gone, Some(404), Validators::default(), 1000);
+        assert_eq!(cache.len(), 2);
+
+        let hit = cache.get_at("https://example.com/a", &accept, 1000 + 3600).unwrap();
+        assert_eq!(hit.url, "https://example.com/a");
+        assert_eq!(hit.message.as_deref(), Some("HTTP 200 (cached)"));
+        assert!(cache.get_at("https://example.com/f", &accept, 1000 + 3600).is_none());
+        assert!(cache.get_at("https://example.com/a", &accept, 1000 + 24 * 3600).is_none());
+
+        assert_eq!(cache.prune_at(1000 + 3600), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_prune_keeps_validators() {
+        let etag = Validators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+        };
+        let mut cache = ResultCache::load(temp_path("prune.json"), CacheTtls::default()).unwrap();
+        let with_etag = LinkCheckResult::new("https://example.com/etag", LinkStatus::Ok, None, Vec::new());
+        let plain = LinkCheckResult::new("https://example.com/plain", LinkStatus::Ok, None, Vec::new());
+        cache.insert_at(&with_etag, Some(200), etag, 0);
+        cache.insert_at(&plain, Some(200), Validators::default(), 0);
+
+        // Two days later only the entry without validators is useless
+        assert_eq!(cache.prune_at(2 * 24 * 3600), 1);
+        assert!(cache.validators("https://example.com/etag", &AcceptPolicy::default()).is_some());
+        assert_eq!(cache.prune_at(REVALIDATE_MAX_AGE.as_secs()), 1);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_accept_policy_changes() {
+        let ttls = CacheTtls::new(&["forbidden=1h".parse().unwrap()]);
+        let mut cache = ResultCache::load(temp_path("accept.json"), ttls).unwrap();
+        let default = AcceptPolicy::default();
+        let accept_403 = AcceptPolicy {
+            default: "200-299,403".parse().unwrap(),
+            per_domain: Vec::new(),
+        };
+
+        // A 403 accepted by --accept 403 isn't ok without it...
+        let accepted = LinkCheckResult::new("https://example.com/a", LinkStatus::Ok, None, Vec::new());
+        cache.insert_at(&accepted, Some(403), Validators::default(), 1000);
+        assert!(cache.get_at("https://example.com/a", &accept_403, 1000).is_some());
+        assert!(cache.get_at("https://example.com/a", &default, 1000).is_none());
+
+        // ...and a cached forbidden isn't one once 403 is accepted
+        let forbidden = LinkStatus::Forbidden { code: 403 };
+        let forbidden = LinkCheckResult::new("https://example.com/f", forbidden, None, Vec::new());
+        cache.insert_at(&forbidden, Some(403), Validators::default(), 1000);
+        assert!(cache.get_at("https://example.com/f", &default, 1000).is_some());
+        assert!(cache.get_at("https://example.com/f", &accept_403, 1000).is_none());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = temp_path("saved.json");
+        let mut cache = ResultCache::load(path.clone(), CacheTtls::default()).unwrap();
+        let ok = LinkCheckResult::new("https://example.com/", LinkStatus::Ok, None, Vec::new());
+        cache.insert(&ok, Some(200), Validators::default());
+        cache.save().unwrap();
+
+        let loaded = ResultCache::load(path.clone(), CacheTtls::default()).unwrap();
+        assert!(loaded.get("https://example.com/", &AcceptPolicy::default()).is_some());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_revalidation() {
+        let etag = Validators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+        };
+        let mut cache = ResultCache::load(temp_path("revalidate.json"), CacheTtls::default()).unwrap();
+
+        // An expired ok result with an ETag can be revalidated...
+        let ok = LinkCheckResult::new("https://example.com/a", LinkStatus::Ok, Some("HTTP 200"), Vec::new());
+        cache.insert_at(&ok, Some(200), etag.clone(), 0);
+        assert!(cache.get("https://example.com/a", &AcceptPolicy::default()).is_none());
+        assert_eq!(cache.validators("https://example.com/a", &AcceptPolicy::default()), Some(etag.clone()));
+        let confirmed = cache.revalidate("https://example.com/a").unwrap();
+        assert_eq!(confirmed.message.as_deref(), Some("HTTP 200 (not modified)"));
+        assert!(cache.get("https://example.com/a", &AcceptPolicy::default()).is_some());
+
+        // ...and so can a page copy
+        cache.insert_page("https://example.com/page", etag.clone(), "<p>hi</p>");
+        assert_eq!(cache.page_validators("https://example.com/page"), Some(etag));
+        assert_eq!(cache.revalidate_page("https://example.com/page").as_deref(), Some("<p>hi</p>"));
+
+        cache.clear();
+        assert!(cache.page_validators("https://example.com/page").is_none());
+    }
+}
@@ -0,0 +1,262 @@
+// src/checker/client.rs
+// =============================================================================
+// This module configures the HTTP clients used to check links and crawl.
+//
+// Settings (with their defaults):
+// - User-Agent: link-guardian/<version>; some sites block unknown agents
+// - Connect timeout: 10s to open the connection (including TLS)
+// - Timeout: 10s for the whole request
+// - Redirects: up to 5 are followed, except to another host when the request
+//   carries --header-for / --cookie-for values or credentials meant only for
+//   the original one (see RequestHeaders::follows_to)
+//
+// Each can be changed for one domain (and its subdomains), so a known-slow
+// host gets a longer timeout without slowing down the whole run:
+//
+//   --timeout 5s --timeout-for legacy.example.com=60s
+//
+// reqwest sets the connect timeout and redirect policy per client, not per
+// request. So there is one client for each distinct combination of settings,
+// created the first time a URL needs it: without per-domain overrides that
+// is a single client, shared by all checks.
+//
+// The proxy and extra CA certificates (see network.rs) are the same for
+// every domain; each client is configured with them.
+//
+// Rust concepts:
+// - Deriving Hash: Using a struct as a HashMap key
+// - Mutex<HashMap>: A cache that many concurrent tasks can fill in
+// =============================================================================
+
+use reqwest::header::HeaderValue;
+use reqwest::redirect::Policy;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::debug;
+
+use super::headers::RequestHeaders;
+use super::network::Network;
+use super::policy::ForDomain;
+use crate::cache::parse_duration;
+
+// The User-Agent sent unless --user-agent says otherwise
+pub const DEFAULT_USER_AGENT: &str = concat!("link-guardian/", env!("CARGO_PKG_VERSION"));
+
+// A timeout like "30s" or "2m" (zero isn't allowed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Timeout(pub Duration);
+
+impl FromStr for Timeout {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match parse_duration(text)? {
+            Duration::ZERO => Err("a timeout must be longer than 0".to_string()),
+            duration => Ok(Timeout(duration)),
+        }
+    }
+}
+
+// A User-Agent like "Mozilla/5.0 (compatible)"
+//
+// Checked when parsing, so building a client with it can't fail later.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UserAgent(pub String);
+
+impl FromStr for UserAgent {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        HeaderValue::from_str(text).map_err(|_| format!("'{}' is not a valid User-Agent", text.escape_debug()))?;
+        Ok(UserAgent(text.to_string()))
+    }
+}
+
+// Client settings for every domain, plus per-domain overrides
+#[derive(Debug, Clone)]
+pub struct ClientSettings {
+    pub user_agent: UserAgent,
+    /// Time allowed to open a connection (including the TLS handshake)
+    pub connect_timeout: Timeout,
+    /// Time allowed for the whole request
+    pub timeout: Timeout,
+    /// How many redirects are followed before giving up
+    pub max_redirects: usize,
+    /// Per-domain overrides; for each setting the most specific domain wins
+    pub user_agent_for: Vec<ForDomain<UserAgent>>,
+    pub connect_timeout_for: Vec<ForDomain<Timeout>>,
+    pub timeout_for: Vec<ForDomain<Timeout>>,
+    pub max_redirects_for: Vec<ForDomain<usize>>,
+    /// Proxy and trusted certificates, for every domain
+    pub network: Arc<Network>,
+}
+
+impl Default for ClientSettings {
+    fn default() -> Self {
+        ClientSettings {
+            user_agent: UserAgent(DEFAULT_USER_AGENT.to_string()),
+            connect_timeout: Timeout(Duration::from_secs(10)),
+            timeout: Timeout(Duration::from_secs(10)),
+            max_redirects: 5,
+            user_agent_for: Vec::new(),
+            connect_timeout_for: Vec::new(),
+            timeout_for: Vec::new(),
+            max_redirects_for: Vec::new(),
+            network: Arc::new(Network::default()),
+        }
+    }
+}
+
+impl ClientSettings {
+    // The settings that apply to `url`, overrides included
+    pub fn for_url(&self, url: &str) -> HostSettings {
+        HostSettings {
+            user_agent: ForDomain::most_specific(&self.user_agent_for, url).unwrap_or(&self.user_agent).0.clone(),
+            connect_timeout: ForDomain::most_specific(&self.connect_timeout_for, url).unwrap_or(&self.connect_timeout).0,
+            timeout: ForDomain::most_specific(&self.timeout_for, url).unwrap_or(&self.timeout).0,
+            max_redirects: *ForDomain::most_specific(&self.max_redirects_for, url).unwrap_or(&self.max_redirects),
+            network: Arc::clone(&self.network),
+        }
+    }
+}
+
+// The settings for one URL, after applying the overrides
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HostSettings {
+    pub user_agent: String,
+    pub connect_timeout: Duration,
+    pub timeout: Duration,
+    pub max_redirects: usize,
+    pub network: Arc<Network>,
+}
+
+impl HostSettings {
+    // Creates a client that follows up to max_redirects redirects
+    //
+    // `headers` are the ones requests will carry: redirects that would send
+    // per-domain values to another host are not followed.
+    pub fn client(&self, headers: &RequestHeaders) -> Client {
+        self.client_with(redirect_policy(self.max_redirects, headers.clone()))
+    }
+
+    // Creates a client with another redirect policy
+    //
+    // `explain` uses Policy::none() to see every redirect hop on its own.
+    pub fn client_with(&self, redirect: Policy) -> Client {
+        let builder = self.network.configure(Client::builder());
+        builder
+            .user_agent(self.user_agent.as_str())
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.timeout)
+            .redirect(redirect)
+            .build()
+            .expect("Failed to create HTTP client")
+    }
+}
+
+// Like Policy::limited, but stops instead of leaking per-domain headers
+//
+// send_following() then sends a new request to the other host.
+fn redirect_policy(max_redirects: usize, headers: RequestHeaders) -> Policy {
+    Policy::custom(move |attempt| {
+        // previous() starts with the URL that was requested
+        let from = attempt.previous().first().map(|url| url.as_str().to_string());
+        if attempt.previous().len() > max_redirects {
+            attempt.error("too many redirects")
+        } else if from.is_some_and(|from| !headers.follows_to(&from, attempt.url().as_str())) {
+            debug!("Not following redirect to {}: per-domain headers are for another host", attempt.url());
+            attempt.stop()
+        } else {
+            attempt.follow()
+        }
+    })
+}
+
+// One client per distinct HostSettings, created when first needed
+//
+// Cloning a Client is cheap, but each one has its own connection pool, so
+// URLs with the same settings share a client.
+#[derive(Debug)]
+pub struct Clients {
+    settings: ClientSettings,
+    headers: RequestHeaders,
+    clients: Mutex<HashMap<HostSettings, Client>>,
+}
+
+impl Clients {
+    // `headers` decide which redirects are safe to follow (see client())
+    pub fn new(settings: ClientSettings, headers: RequestHeaders) -> Self {
+        Clients {
+            settings,
+            headers,
+            clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // The client to use for `url`
+    pub fn for_url(&self, url: &str) -> Client {
+        let settings = self.settings.for_url(url);
+        let mut clients = self.clients.lock().unwrap();
+        clients.entry(settings).or_insert_with_key(|settings| settings.client(&self.headers)).clone()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. What does concat!(..., env!("CARGO_PKG_VERSION")) do?
+//    - env! reads the version from Cargo.toml while compiling
+//    - concat! glues string literals together, also at compile time
+//    - The result is a plain &'static str: "link-guardian/0.1.0"
+//
+// 2. Why can HostSettings be a HashMap key?
+//    - #[derive(Hash, PartialEq, Eq)] lets Rust hash and compare it
+//    - Duration, String and usize already implement these traits
+//    - Arc<Network> hashes the Network it points to; cloning the Arc for
+//      every URL is cheap, copying the certificates would not be
+//
+// 3. What does or_insert_with_key do?
+//    - If the key is missing, it calls the function with the key to create
+//      the value, inserts it, and returns a reference either way
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!("30s".parse::<Timeout>().unwrap(), Timeout(Duration::from_secs(30)));
+        assert!("0".parse::<Timeout>().is_err());
+        assert!("soon".parse::<Timeout>().is_err());
+    }
+
+    #[test]
+    fn test_per_domain_overrides() {
+        let settings = ClientSettings {
+            timeout_for: vec!["slow.example.com=2m".parse().unwrap()],
+            max_redirects_for: vec!["example.com=10".parse().unwrap()],
+            user_agent_for: vec!["picky.org=Mozilla/5.0".parse().unwrap()],
+            ..ClientSettings::default()
+        };
+
+        let slow = settings.for_url("https://slow.example.com/report");
+        assert_eq!(slow.timeout, Duration::from_secs(120));
+        assert_eq!(slow.max_redirects, 10);
+        assert_eq!(slow.user_agent, DEFAULT_USER_AGENT);
+
+        let picky = settings.for_url("https://picky.org/");
+        assert_eq!(picky.timeout, Duration::from_secs(10));
+        assert_eq!(picky.user_agent, "Mozilla/5.0");
+
+        // Two domains with the same settings share a client
+        let clients = Clients::new(settings, RequestHeaders::default());
+        clients.for_url("https://a.com/");
+        clients.for_url("https://b.com/");
+        clients.for_url("https://slow.example.com/");
+        assert_eq!(clients.clients.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,437 @@
+// src/checker/comments.rs
+// =============================================================================
+// This module extracts links from comments in source code.
+//
+// Doc comments and docstrings are full of URLs (specs, issue trackers,
+// upstream docs) that nobody ever clicks again. We find comments using a
+// small scanner per language family and pull the URLs out of them:
+//
+// - C-like languages (Rust, C, Go, Java, JS/TS, ...): //, ///, //!, /* */, /** */
+// - Script languages (Python, Ruby, Shell, YAML, ...): # comments (in Shell,
+//   Perl, YAML and TOML only at the start of a word, so `$#` is not one)
+// - Python: triple-quoted strings, which is how docstrings are written
+//
+// String literals are skipped so "https://example.com" in code (which contains
+// "//") is not mistaken for a comment. So are character literals like '"' and
+// Go's `raw strings`, which would otherwise look like the start of a string.
+//
+// Rust concepts:
+// - Static data: A table of comment syntaxes known at compile time
+// - Byte offsets: Walking a &str with char_indices() and slicing safely
+// =============================================================================
+
+use super::location::TextPosition;
+
+// How comments and strings look in a family of languages
+struct CommentSyntax {
+    /// Markers that start a comment running to the end of the line
+    line: &'static [&'static str],
+    /// Start and end markers of block comments
+    block: Option<(&'static str, &'static str)>,
+    /// Characters that start (and end) a string literal
+    quotes: &'static [char],
+    /// Character literals in single quotes: 'a', '"', '\n'
+    char_literals: bool,
+    /// Quote of raw strings, which have no escapes and may span lines
+    raw_quote: Option<char>,
+    /// Treat triple-quoted strings as comments (Python docstrings)
+    docstrings: bool,
+    /// Line comments only start at the beginning of a line or after
+    /// whitespace (so shell's `$#` and `${#var}` aren't comments)
+    line_after_space: bool,
+}
+
+// C, Rust, Java, Kotlin, C#, ...
+// (' is only a string for one character, since Rust also uses it for
+// lifetimes like 'a)
+const C_LIKE: CommentSyntax = CommentSyntax {
+    line: &["//"],
+    block: Some(("/*", "*/")),
+    quotes: &['"'],
+    char_literals: true,
+    raw_quote: None,
+    docstrings: false,
+    line_after_space: false,
+};
+
+const GO: CommentSyntax = CommentSyntax {
+    line: &["//"],
+    block: Some(("/*", "*/")),
+    quotes: &['"'],
+    char_literals: true,
+    raw_quote: Some('`'),
+    docstrings: false,
+    line_after_space: false,
+};
+
+const JS_LIKE: CommentSyntax = CommentSyntax {
+    line: &["//"],
+    block: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    char_literals: false,
+    raw_quote: None,
+    docstrings: false,
+    line_after_space: false,
+};
+
+const HASH: CommentSyntax = CommentSyntax {
+    line: &["#"],
+    block: None,
+    quotes: &['"', '\''],
+    char_literals: false,
+    raw_quote: None,
+    docstrings: false,
+    line_after_space: false,
+};
+
+// Shell, Perl, YAML and TOML, where "#" inside a word isn't a comment
+const SHELL: CommentSyntax = CommentSyntax {
+    line: &["#"],
+    block: None,
+    quotes: &['"', '\''],
+    char_literals: false,
+    raw_quote: None,
+    docstrings: false,
+    line_after_space: true,
+};
+
+const PYTHON: CommentSyntax = CommentSyntax {
+    line: &["#"],
+    block: None,
+    quotes: &['"', '\''],
+    char_literals: false,
+    raw_quote: None,
+    docstrings: true,
+    line_after_space: false,
+};
+
+// Picks the comment syntax based on the file extension
+fn syntax_for(path: &str) -> Option<&'static CommentSyntax> {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+
+    // Minified bundles are generated code, not something people maintain
+    if file_name.ends_with(".min.js") {
+        return None;
+    }
+
+    let extension = file_name.rsplit_once('.').map(|(_, ext)| ext)?;
+    match extension {
+        "rs" | "c" | "h" | "cc" | "cpp" | "hpp" | "java" | "kt" | "swift" | "scala" | "cs" => Some(&C_LIKE),
+        "go" => Some(&GO),
+        "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => Some(&JS_LIKE),
+        "py" | "pyi" => Some(&PYTHON),
+        "rb" | "r" => Some(&HASH),
+        "sh" | "bash" | "zsh" | "pl" | "yml" | "yaml" | "toml" => Some(&SHELL),
+        _ => None,
+    }
+}
+
+// Returns true if we know how to find comments in this file
+pub fn is_source_file(path: &str) -> bool {
+    syntax_for(path).is_some()
+}
+
+// Extracts all HTTP/HTTPS links from comments in a source file
+//
+// Parameters:
+//   source: the file contents
+//   path: the file path (the extension decides the comment syntax)
+//
+// Returns: Vec of (url, position) for every URL found inside a comment.
+// Unknown file types return an empty Vec.
+//
+// Example:
+//   source = "let x = 1; // see https://doc.rust-lang.org/std/"
+//   path   = "src/lib.rs"
+//   result = [("https://doc.rust-lang.org/std/", line 1, column 19)]
+pub fn extract_comment_links(source: &str, path: &str) -> Vec<(String, TextPosition)> {
+    let syntax = match syntax_for(path) {
+        Some(syntax) => syntax,
+        None => return Vec::new(),
+    };
+
+    let mut links = Vec::new();
+    for (start, end) in comment_ranges(source, syntax) {
+        for (offset, url) in find_urls(&source[start..end]) {
+            links.push((url, TextPosition::from_offset(source, start + offset)));
+        }
+    }
+    links
+}
+
+// Finds the byte ranges of every comment in the source
+//
+// This is a tiny state machine: we're either in code, or we've just seen
+// the start of a comment/string and skip ahead to where it ends.
+fn comment_ranges(source: &str, syntax: &CommentSyntax) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut i = 0;
+
+    while i < source.len() {
+        let rest = &source[i..];
+
+        // Python docstrings: """...""" or '''...'''
+        if syntax.docstrings {
+            if let Some(marker) = ["\"\"\"", "'''"].into_iter().find(|m| rest.starts_with(*m)) {
+                let body = i + marker.len();
+                let end = source[body..].find(marker).map(|e| body + e).unwrap_or(source.len());
+                ranges.push((body, end));
+                i = (end + marker.len()).min(source.len());
+                continue;
+            }
+        }
+
+        // Line comments run to the end of the line
+        let at_word_start = !syntax.line_after_space
+            || source[..i].chars().next_back().is_none_or(char::is_whitespace);
+        let line_marker = syntax.line.iter().find(|m| rest.starts_with(**m));
+        if let Some(marker) = line_marker.filter(|_| at_word_start) {
+            let body = i + marker.len();
+            let end = source[body..].find('\n').map(|e| body + e).unwrap_or(source.len());
+            ranges.push((body, end));
+            i = end;
+            continue;
+        }
+
+        // Block comments run to their closing marker
+        if let Some((open, close)) = syntax.block {
+            if rest.starts_with(open) {
+                let body = i + open.len();
+                let end = source[body..].find(close).map(|e| body + e).unwrap_or(source.len());
+                ranges.push((body, end));
+                i = (end + close.len()).min(source.len());
+                continue;
+            }
+        }
+
+        let c = rest.chars().next().unwrap_or(' ');
+
+        // String literals are skipped entirely
+        if syntax.quotes.contains(&c) {
+            i = string_end(source, i + c.len_utf8(), c);
+            continue;
+        }
+        if syntax.raw_quote == Some(c) {
+            let body = i + c.len_utf8();
+            i = source[body..].find(c).map(|e| body + e + c.len_utf8()).unwrap_or(source.len());
+            continue;
+        }
+        if c == '\'' && syntax.char_literals {
+            if let Some(end) = char_literal_end(source, i) {
+                i = end;
+                continue;
+            }
+        }
+
+        i += c.len_utf8();
+    }
+
+    ranges
+}
+
+// Returns the byte offset just past the string literal starting at `start`
+//
+// Handles backslash escapes (\"), and stops at the end of the line for
+// unterminated strings so a stray quote can't swallow the rest of the file.
+// Backtick template strings in JavaScript may span lines.
+fn string_end(source: &str, start: usize, quote: char) -> usize {
+    let mut escaped = false;
+    for (offset, c) in source[start..].char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return start + offset + c.len_utf8();
+        } else if c == '\n' && quote != '`' {
+            return start + offset;
+        }
+    }
+    source.len()
+}
+
+// Returns the byte offset just past the character literal starting at `start`
+//
+// None if the quote doesn't start one, like a Rust lifetime ('a) or loop
+// label ('outer: loop).
+//   'x'  '"'  '\''  '\n'  '\u{1F600}'
+fn char_literal_end(source: &str, start: usize) -> Option<usize> {
+    let body = start + 1;
+    let mut chars = source[body..].char_indices();
+    let (offset, _) = match chars.next()? {
+        // After a backslash the next character is escaped, and a few more
+        // may follow ('\x7f', '\u{...}') before the closing quote
+        (_, '\\') => chars.skip(1).take(10).take_while(|&(_, c)| c != '\n').find(|&(_, c)| c == '\'')?,
+        (_, '\'' | '\n') => return None,
+        _ => chars.next().filter(|&(_, c)| c == '\'')?,
+    };
+    Some(body + offset + 1)
+}
+
+// Finds URLs in free-form text
+//
+// Returns (byte offset, url) pairs. A URL runs until whitespace or a
+// character that usually surrounds links in prose (quotes, <>, backticks).
+// Trailing punctuation like "." or an unmatched ")" is trimmed:
+//   "see https://a.com/x." -> "https://a.com/x"
+//   "(https://a.com/x)"    -> "https://a.com/x"
+fn find_urls(text: &str) -> Vec<(usize, String)> {
+    let mut urls = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(found) = next_url_start(&text[search_from..]) {
+        let start = search_from + found;
+        let end = text[start..]
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>' | '`'))
+            .map(|e| start + e)
+            .unwrap_or(text.len());
+
+        let url = trim_url(&text[start..end]);
+        if url.len() > "https://".len() {
+            urls.push((start, url.to_string()));
+        }
+        search_from = end;
+    }
+
+    urls
+}
+
+// Finds the earliest "http://" or "https://" in the text
+fn next_url_start(text: &str) -> Option<usize> {
+    match (text.find("http://"), text.find("https://")) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+// Removes punctuation that belongs to the sentence, not the URL
+fn trim_url(url: &str) -> &str {
+    let mut url = url;
+    loop {
+        let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '*']);
+        let trimmed = match trimmed.chars().last() {
+            // Only drop a closing bracket if it has no opening partner in the URL
+            Some(close @ (')' | ']' | '}')) => {
+                let open = match close {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                if trimmed.matches(open).count() < trimmed.matches(close).count() {
+                    &trimmed[..trimmed.len() - 1]
+                } else {
+                    trimmed
+                }
+            }
+            _ => trimmed,
+        };
+        if trimmed == url {
+            return url;
+        }
+        url = trimmed;
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why not use a regex?
+//    - A regex can find URLs, but not whether they're inside a comment
+//    - "let url = \"https://x\";" has "//" inside a string, not a comment
+//    - Walking the text once and tracking state handles both
+//
+// 2. Why &'static CommentSyntax?
+//    - Our syntax tables are constants baked into the binary
+//    - 'static means the reference is valid for the whole program
+//
+// 3. Why byte offsets and char_indices()?
+//    - Rust strings are UTF-8, so one character can be several bytes
+//    - Slicing in the middle of a character panics
+//    - char_indices() gives us byte offsets that are always safe to slice at
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(source: &str, path: &str) -> Vec<String> {
+        extract_comment_links(source, path).into_iter().map(|(url, _)| url).collect()
+    }
+
+    #[test]
+    fn test_rust_comments() {
+        let source = r#"
+//! Crate docs: https://docs.rs/mycrate
+/// See [the spec](https://spec.example.com/v1).
+fn main() {
+    let api = "https://api.example.com"; // https://issues.example.com/42
+    /* block https://block.example.com */
+}
+"#;
+        assert_eq!(
+            urls(source, "src/main.rs"),
+            vec![
+                "https://docs.rs/mycrate",
+                "https://spec.example.com/v1",
+                "https://issues.example.com/42",
+                "https://block.example.com",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_char_literals() {
+        let source = r#"
+fn f<'a>(s: &'a str) {
+    let q = '"'; // see https://yes.example.com
+    let e = '\''; 'outer: loop {} // https://also.example.com
+}
+"#;
+        assert_eq!(
+            urls(source, "src/lib.rs"),
+            vec!["https://yes.example.com", "https://also.example.com"]
+        );
+    }
+
+    #[test]
+    fn test_go_raw_strings() {
+        let source = r#"
+var re = `http://x // y \`
+var doc = `
+// https://no.example.com
+` // https://yes.example.com
+"#;
+        assert_eq!(urls(source, "main.go"), vec!["https://yes.example.com"]);
+    }
+
+    #[test]
+    fn test_python_comments_and_docstrings() {
+        let source = "def f():\n    \"\"\"Docs at https://py.example.com.\"\"\"\n    x = '#not-a-comment https://no.example.com'  # https://yes.example.com\n";
+        assert_eq!(
+            urls(source, "tool.py"),
+            vec!["https://py.example.com", "https://yes.example.com"]
+        );
+    }
+
+    #[test]
+    fn test_shell_hash_inside_words() {
+        let source = "echo $# ${#name} https://no.example.com\n# https://yes.example.com\nls # https://also.example.com\n";
+        assert_eq!(
+            urls(source, "build.sh"),
+            vec!["https://yes.example.com", "https://also.example.com"]
+        );
+    }
+
+    #[test]
+    fn test_positions() {
+        let source = "fn a() {}\n    // see https://example.com";
+        let links = extract_comment_links(source, "lib.rs");
+        assert_eq!(links[0].1, TextPosition { line: 2, column: 12 });
+    }
+
+    #[test]
+    fn test_unknown_file_type() {
+        assert!(!is_source_file("image.png"));
+        assert!(urls("// https://example.com", "notes.txt").is_empty());
+    }
+}
@@ -0,0 +1,208 @@
+// src/checker/css.rs
+// =============================================================================
+// This module extracts links from CSS.
+//
+// Stylesheets reference other resources in two ways:
+// - url(...)    e.g. background-image: url("/img/hero.png");
+// - @import     e.g. @import "theme.css";  or  @import url(theme.css);
+//
+// Fonts and images referenced like this break silently when assets are moved,
+// so we treat them like any other link. The same parser is used for
+// stylesheet files, <style> blocks and inline style="..." attributes.
+//
+// Rust concepts:
+// - String searching: find() and slicing to walk through text
+// - Reusing helpers: relative URLs are resolved the same way as in html.rs
+// =============================================================================
+
+use url::Url;
+
+// Extracts all HTTP/HTTPS links from CSS
+//
+// Parameters:
+//   css: the CSS text (a whole stylesheet or a style="" attribute)
+//   base_url: URL of the stylesheet or page (for resolving relative links)
+//
+// Returns: Vec<String> containing all absolute URLs found
+//
+// Example:
+//   css = "body { background: url('/bg.png') }"
+//   base_url = "https://example.com/css/site.css"
+//   result = ["https://example.com/bg.png"]
+pub fn extract_css_links(css: &str, base_url: &str) -> Vec<String> {
+    let base = match Url::parse(base_url) {
+        Ok(url) => url,
+        Err(_) => return Vec::new(),
+    };
+
+    // Comments can contain url() examples that aren't real references
+    let css = strip_comments(css);
+
+    css_references(&css)
+        .into_iter()
+        .filter_map(|reference| base.join(&reference).ok())
+        .filter(|url| url.scheme() == "http" || url.scheme() == "https")
+        .map(|url| url.to_string())
+        .collect()
+}
+
+// Extracts only the @import targets from a stylesheet
+//
+// The crawler uses this to follow imported stylesheets on the same site.
+pub fn extract_css_imports(css: &str, base_url: &str) -> Vec<String> {
+    let base = match Url::parse(base_url) {
+        Ok(url) => url,
+        Err(_) => return Vec::new(),
+    };
+
+    let css = strip_comments(css);
+    let mut imports = Vec::new();
+    let mut rest = css.as_str();
+
+    while let Some(start) = rest.find("@import") {
+        rest = &rest[start + "@import".len()..];
+        let target = rest.trim_start();
+        let reference = match target.strip_prefix("url(") {
+            Some(argument) => read_url_function(argument),
+            None => read_quoted(target),
+        };
+        if let Some(url) = reference.and_then(|r| base.join(&r).ok()) {
+            imports.push(url.to_string());
+        }
+    }
+
+    imports
+}
+
+// Finds every url(...) and @import "..." reference (unresolved)
+fn css_references(css: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    let mut i = 0;
+
+    while i < css.len() {
+        let rest = &css[i..];
+
+        if let Some(argument) = rest.strip_prefix("url(") {
+            if let Some(reference) = read_url_function(argument) {
+                references.push(reference);
+            }
+            i += "url(".len();
+        } else if let Some(target) = rest.strip_prefix("@import") {
+            // @import url(...) is picked up by the url( branch on a later
+            // iteration, so only handle the plain string form here
+            let target = target.trim_start();
+            if !target.starts_with("url(") {
+                if let Some(reference) = read_quoted(target) {
+                    references.push(reference);
+                }
+            }
+            i += "@import".len();
+        } else {
+            i += rest.chars().next().map(char::len_utf8).unwrap_or(1);
+        }
+    }
+
+    references
+}
+
+// Reads the argument of url( ... ) - the text after the opening parenthesis
+//
+// Handles url("a.png"), url('a.png') and url(a.png).
+// data: URIs are skipped; they are inline content, not links.
+fn read_url_function(text: &str) -> Option<String> {
+    let text = text.trim_start();
+    let reference = if text.starts_with('"') || text.starts_with('\'') {
+        read_quoted(text)?
+    } else {
+        let end = text.find(')')?;
+        text[..end].trim().to_string()
+    };
+
+    if reference.is_empty() || reference.starts_with("data:") || reference.starts_with('#') {
+        None
+    } else {
+        Some(reference)
+    }
+}
+
+// Reads a "double" or 'single' quoted string at the start of the text
+fn read_quoted(text: &str) -> Option<String> {
+    let quote = text.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let body = &text[1..];
+    let end = body.find(quote)?;
+    Some(body[..end].to_string())
+}
+
+// Removes /* ... */ comments from CSS
+fn strip_comments(css: &str) -> String {
+    let mut output = String::with_capacity(css.len());
+    let mut rest = css;
+
+    while let Some(start) = rest.find("/*") {
+        output.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    output.push_str(rest);
+
+    output
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why not a full CSS parser?
+//    - We only need url() and @import, not selectors or properties
+//    - A small scanner is enough and keeps dependencies down
+//
+// 2. What does base.join() do here?
+//    - CSS urls are relative to the STYLESHEET, not the page using it
+//    - That's why callers pass the stylesheet's own URL as base_url
+//    - For style="" attributes and <style> blocks the page URL is the base
+//
+// 3. What is .filter_map()?
+//    - Combines filter and map: the closure returns an Option
+//    - Some(x) keeps x, None drops the item
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_function_forms() {
+        let css = r#"
+            .a { background: url("/img/a.png"); }
+            .b { background: url('b.png'); }
+            .c { background: url( https://cdn.example.com/c.png ); }
+            .d { background: url(data:image/png;base64,AAAA); }
+        "#;
+        let links = extract_css_links(css, "https://example.com/css/site.css");
+        assert_eq!(
+            links,
+            vec![
+                "https://example.com/img/a.png",
+                "https://example.com/css/b.png",
+                "https://cdn.example.com/c.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_imports() {
+        let css = r#"@import "theme.css"; @import url(print.css) print; /* url(skip.png) */"#;
+        let base = "https://example.com/css/site.css";
+        assert_eq!(
+            extract_css_links(css, base),
+            vec!["https://example.com/css/theme.css", "https://example.com/css/print.css"]
+        );
+        assert_eq!(extract_css_imports(css, base), extract_css_links(css, base));
+    }
+
+    #[test]
+    fn test_invalid_base_url() {
+        assert!(extract_css_links("a { background: url(x.png) }", "not a url").is_empty());
+    }
+}
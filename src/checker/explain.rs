@@ -0,0 +1,495 @@
+// src/checker/explain.rs
+// =============================================================================
+// This module explains, step by step, how one link gets its status.
+//
+// When a link fails, the report only says "ssl_error" or "broken (404)".
+// `link-guardian explain <url>` shows the whole story instead of making you
+// reproduce it with curl:
+//
+// 1. URL: what was parsed out of it (scheme, host, port)
+// 2. DNS: which addresses the host name resolves to, and how long it took
+// 3. TCP: how long connecting to the first address took
+// 4. TLS (https only): protocol version, cipher suite and certificate chain
+// 5. Redirects: every HEAD request with its status and response headers,
+//    following Location headers one hop at a time like the checker does,
+//    including where it stops to keep per-domain headers on their host
+// 6. HEAD vs GET: some servers answer HEAD differently, so GET is tried too
+// 7. Decision: the status the checker gives the link, and the rule behind it
+//
+// The decision comes from check_single_link(), the same code a scan runs, so
+// it can't drift from what a scan reports. Steps 2-4 are done separately (the
+// checker leaves them to reqwest, which doesn't expose timings or TLS
+// details). The TLS step trusts the same roots as reqwest: webpki-roots plus
+// any --ca-cert. If verification fails, rustls stops the handshake before
+// we can look at the certificates, so only the error is shown.
+//
+// Steps 2-4 always connect directly. With a proxy (--proxy or HTTPS_PROXY),
+// step 1 says so: the requests in steps 5-7 go through the proxy, so a
+// direct connection failing there doesn't mean the link is broken.
+//
+// Rust concepts:
+// - fmt::Write: Building a long text with writeln!, like printing to a String
+// - tokio::time::timeout: Giving up on a future that takes too long
+// - Arc: Sharing the TLS configuration with the connector
+// =============================================================================
+
+use reqwest::redirect::Policy;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::{ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use url::Url;
+
+use super::client::HostSettings;
+use super::http::{check_single_link, root_cause, send_following, CheckOptions, LinkCheckResult, LinkStatus};
+
+// The trace of one explained link, and the status the checker gave it
+pub struct Explanation {
+    /// Human-readable trace, one section per step
+    pub text: String,
+    /// The same result a scan would report for this URL
+    pub result: LinkCheckResult,
+}
+
+// Explains how `url` is checked
+//
+// Parameters:
+//   url: the link to explain
+//   options: accepted status codes etc. (the cache is never used here)
+//
+// Returns: the trace and the final result
+//
+// Example:
+//   let explanation = explain("https://example.com/gone", &options).await;
+//   print!("{}", explanation.text);
+pub async fn explain(url: &str, options: &CheckOptions) -> Explanation {
+    let mut out = String::new();
+    // Writing to a String never fails, so the fmt::Results are ignored
+    let _ = writeln!(out, "🔎 Explaining {}\n", url);
+
+    // User-Agent, timeouts and redirect limit for this URL's domain
+    let settings = options.client.for_url(url);
+
+    if let Some((host, port, https)) = describe_url(&mut out, url, &settings) {
+        if let Some(address) = resolve(&mut out, &host, port).await {
+            if let Some(stream) = connect(&mut out, address, &settings).await {
+                if https {
+                    handshake(&mut out, &host, stream, &settings).await;
+                }
+            }
+        }
+    }
+
+    let (head_code, stopped) = follow_redirects(&mut out, url, options, &settings).await;
+    compare_get(&mut out, url, head_code, options, &settings).await;
+
+    // The real check, without the cache so it always goes to the network
+    let options = CheckOptions {
+        cache: None,
+        ..options.clone()
+    };
+    let result = check_single_link(settings.client(&options.headers), url.to_string(), &options).await;
+
+    let _ = writeln!(out, "7. Decision");
+    let _ = writeln!(out, "   Status: {}", result.status.name());
+    if let Some(message) = &result.message {
+        let _ = writeln!(out, "   Message: {}", message);
+    }
+    if let Some(target) = stopped {
+        let _ = writeln!(
+            out,
+            "   Rule: stopped: per-domain headers are for another host, so {} got a request of its own",
+            target
+        );
+    }
+    let _ = writeln!(out, "   Rule: {}", rule(&result.status, &settings));
+
+    Explanation { text: out, result }
+}
+
+// Step 1: host, port and whether TLS is involved
+//
+// Returns None (after saying why) if there is nothing to connect to.
+fn describe_url(out: &mut String, url: &str, settings: &HostSettings) -> Option<(String, u16, bool)> {
+    let _ = writeln!(out, "1. URL");
+    let parsed = match Url::parse(url) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let _ = writeln!(out, "   ❌ Not a valid URL: {}\n", e);
+            return None;
+        }
+    };
+
+    let host = parsed.host_str().map(|host| host.trim_matches(|c| c == '[' || c == ']').to_string());
+    let (host, port) = match (host, parsed.port_or_known_default()) {
+        (Some(host), Some(port)) if matches!(parsed.scheme(), "http" | "https") => (host, port),
+        _ => {
+            let _ = writeln!(out, "   ❌ Only http and https URLs with a host can be checked\n");
+            return None;
+        }
+    };
+
+    let _ = writeln!(out, "   Scheme {}, host {}, port {}, path {}", parsed.scheme(), host, port, parsed.path());
+    let _ = writeln!(
+        out,
+        "   User-Agent {}, connect timeout {:?}, timeout {:?}, up to {} redirects",
+        settings.user_agent, settings.connect_timeout, settings.timeout, settings.max_redirects
+    );
+    if let Some(proxy) = settings.network.describe_proxy() {
+        let _ = writeln!(out, "   Proxy {}", proxy);
+        let _ = writeln!(out, "   (steps 2-4 connect directly; the requests use the proxy unless --no-proxy/NO_PROXY exempts the host)");
+    }
+    for bundle in &settings.network.ca_certs {
+        let count = bundle.certificates.len();
+        let _ = writeln!(out, "   Also trusting {} certificate(s) from {}", count, bundle.path.display());
+    }
+    let _ = writeln!(out);
+    Some((host, port, parsed.scheme() == "https"))
+}
+
+// Step 2: DNS lookup; returns the first address
+async fn resolve(out: &mut String, host: &str, port: u16) -> Option<SocketAddr> {
+    let started = Instant::now();
+    let lookup = tokio::net::lookup_host((host, port)).await;
+    let _ = writeln!(out, "2. DNS ({} ms)", started.elapsed().as_millis());
+
+    let addresses: Vec<SocketAddr> = match lookup {
+        Ok(addresses) => addresses.collect(),
+        Err(e) => {
+            let _ = writeln!(out, "   ❌ {} does not resolve: {}\n", host, e);
+            return None;
+        }
+    };
+    for address in &addresses {
+        let _ = writeln!(out, "   {}", address.ip());
+    }
+    let _ = writeln!(out);
+    addresses.first().copied()
+}
+
+// Step 3: TCP connection to the first address
+async fn connect(out: &mut String, address: SocketAddr, settings: &HostSettings) -> Option<TcpStream> {
+    let started = Instant::now();
+    let connected = tokio::time::timeout(settings.connect_timeout, TcpStream::connect(address)).await;
+    let elapsed = started.elapsed().as_millis();
+
+    match connected {
+        Ok(Ok(stream)) => {
+            let _ = writeln!(out, "3. TCP connect to {} ({} ms)\n", address, elapsed);
+            Some(stream)
+        }
+        Ok(Err(e)) => {
+            let _ = writeln!(out, "3. TCP connect to {} ({} ms)\n   ❌ {} ({:?})\n", address, elapsed, e, e.kind());
+            None
+        }
+        Err(_) => {
+            let _ = writeln!(out, "3. TCP connect to {}\n   ❌ No answer within {:?}\n", address, settings.connect_timeout);
+            None
+        }
+    }
+}
+
+// Step 4: TLS handshake with the same trusted roots as reqwest (and --ca-cert)
+async fn handshake(out: &mut String, host: &str, stream: TcpStream, settings: &HostSettings) {
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+        OwnedTrustAnchor::from_subject_spki_name_constraints(anchor.subject, anchor.spki, anchor.name_constraints)
+    }));
+    for bundle in &settings.network.ca_certs {
+        roots.add_parsable_certificates(&bundle.certificates);
+    }
+    let mut config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    let server_name = match ServerName::try_from(host) {
+        Ok(name) => name,
+        Err(e) => {
+            let _ = writeln!(out, "4. TLS\n   ❌ {} can't be used as a TLS server name: {}\n", host, e);
+            return;
+        }
+    };
+
+    let started = Instant::now();
+    let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+    let connected = tokio::time::timeout(settings.connect_timeout, connector.connect(server_name, stream)).await;
+    let _ = writeln!(out, "4. TLS handshake ({} ms)", started.elapsed().as_millis());
+
+    let tls = match connected {
+        Ok(Ok(tls)) => tls,
+        Ok(Err(e)) => {
+            let _ = writeln!(out, "   ❌ {}\n", e);
+            return;
+        }
+        Err(_) => {
+            let _ = writeln!(out, "   ❌ No answer within {:?}\n", settings.connect_timeout);
+            return;
+        }
+    };
+
+    let (_, session) = tls.get_ref();
+    let version = session.protocol_version().map_or("unknown".to_string(), |v| format!("{:?}", v));
+    let cipher = session.negotiated_cipher_suite().map_or("unknown".to_string(), |c| format!("{:?}", c.suite()));
+    let alpn = session.alpn_protocol().map_or("none".to_string(), |p| String::from_utf8_lossy(p).to_string());
+    let _ = writeln!(out, "   {}, {}, ALPN {}", version, cipher, alpn);
+
+    let chain = session.peer_certificates().unwrap_or(&[]);
+    let _ = writeln!(out, "   Certificate chain ({}):", chain.len());
+    for (index, certificate) in chain.iter().enumerate() {
+        let _ = writeln!(out, "   [{}] {}", index, describe_certificate(&certificate.0));
+    }
+    let _ = writeln!(out);
+}
+
+// Subject, issuer and validity of a DER-encoded certificate
+fn describe_certificate(der: &[u8]) -> String {
+    match x509_parser::parse_x509_certificate(der) {
+        Ok((_, certificate)) => {
+            let validity = certificate.validity();
+            format!(
+                "{}\n       issued by {}\n       valid {} to {}",
+                certificate.subject(),
+                certificate.issuer(),
+                validity.not_before,
+                validity.not_after
+            )
+        }
+        Err(e) => format!("(could not parse certificate: {})", e),
+    }
+}
+
+// Step 5: HEAD requests, one redirect hop at a time
+//
+// Lists the names (not values) of the extra headers sent with --header,
+// --cookie, --bearer-for etc. Like the client, a redirect keeps the headers
+// of the first request, unless they include per-domain ones the next host
+// shouldn't get: there the checker stops and sends a new request with that
+// host's headers (see send_following).
+//
+// Returns the status code of the last response, if there was one, and the
+// last URL the checker had to send a new request to.
+async fn follow_redirects(
+    out: &mut String,
+    url: &str,
+    options: &CheckOptions,
+    settings: &HostSettings,
+) -> (Option<u16>, Option<String>) {
+    let max_redirects = settings.max_redirects;
+    let _ = writeln!(out, "5. HEAD requests (redirects followed one at a time, at most {})", max_redirects);
+    let client = settings.client_with(Policy::none());
+    let mut current = url.to_string();
+    // The URL whose headers the current request carries
+    let mut origin = url.to_string();
+    let mut last_code = None;
+    let mut stopped = None;
+
+    for hop in 0..=max_redirects {
+        let started = Instant::now();
+        let _ = writeln!(out, "   → HEAD {}", current);
+        let request = options.headers.apply(&origin, client.head(&current));
+        let extra: Vec<String> = match request.try_clone().and_then(|r| r.build().ok()) {
+            Some(built) => built.headers().keys().map(|name| name.to_string()).collect(),
+            None => Vec::new(),
+        };
+        if !extra.is_empty() {
+            let _ = writeln!(out, "     Sending: {}", extra.join(", "));
+        }
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                let _ = writeln!(out, "     ❌ {} ({} ms)\n", root_cause(&e), started.elapsed().as_millis());
+                return (last_code, stopped);
+            }
+        };
+
+        let status = response.status();
+        last_code = Some(status.as_u16());
+        let _ = writeln!(out, "     {} ({} ms)", status, started.elapsed().as_millis());
+        for (name, value) in response.headers() {
+            let _ = writeln!(out, "     {}: {}", name, value.to_str().unwrap_or("(binary)"));
+        }
+
+        let location = response.headers().get("location").and_then(|v| v.to_str().ok());
+        let next = match location {
+            Some(location) if status.is_redirection() => Url::parse(&current).and_then(|base| base.join(location)),
+            _ => break,
+        };
+        match next {
+            Ok(next) if hop < max_redirects => {
+                current = next.to_string();
+                if !options.headers.follows_to(&origin, &current) {
+                    let _ = writeln!(out, "     ↳ Stopped: per-domain headers are for another host");
+                    let _ = writeln!(out, "       The checker sends a new request with the headers for {}", current);
+                    origin = current.clone();
+                    stopped = Some(current.clone());
+                }
+            }
+            Ok(_) => {
+                let _ = writeln!(out, "     ❌ More than {} redirects, the checker gives up here", max_redirects);
+                break;
+            }
+            Err(e) => {
+                let _ = writeln!(out, "     ❌ Location is not a valid URL: {}", e);
+                break;
+            }
+        }
+    }
+
+    let _ = writeln!(out);
+    (last_code, stopped)
+}
+
+// Step 6: a GET request, to compare with HEAD
+//
+// The checker decides on HEAD alone; a server that answers GET differently
+// (often 405 Method Not Allowed for HEAD) shows up here.
+async fn compare_get(
+    out: &mut String,
+    url: &str,
+    head_code: Option<u16>,
+    options: &CheckOptions,
+    settings: &HostSettings,
+) {
+    let _ = writeln!(out, "6. GET for comparison (redirects followed)");
+    let started = Instant::now();
+    let client = settings.client(&options.headers);
+
+    let get = |target: &str| options.headers.apply(target, client.get(target));
+    let get_code = match send_following(url, options, get).await {
+        Ok(Some(response)) => {
+            let _ = writeln!(out, "   {} from {} ({} ms)", response.status(), response.url(), started.elapsed().as_millis());
+            Some(response.status().as_u16())
+        }
+        Ok(None) => {
+            let _ = writeln!(out, "   ❌ More than {} redirects ({} ms)", settings.max_redirects, started.elapsed().as_millis());
+            None
+        }
+        Err(e) => {
+            let _ = writeln!(out, "   ❌ {} ({} ms)", root_cause(&e), started.elapsed().as_millis());
+            None
+        }
+    };
+
+    let verdict = match (head_code, get_code) {
+        (Some(head), Some(get)) if head == get => "Same status as HEAD".to_string(),
+        (Some(head), Some(get)) => {
+            format!("HEAD got {} but GET got {}; the checker goes by HEAD", head, get)
+        }
+        _ => "No status to compare".to_string(),
+    };
+    let _ = writeln!(out, "   {}\n", verdict);
+}
+
+// The rule in check_single_link() that leads to each status
+fn rule(status: &LinkStatus, settings: &HostSettings) -> String {
+    let not_accepted = |code: &u16, rest: &str| format!("HTTP {} is not an accepted code (--accept), {}", code, rest);
+
+    match status {
+        LinkStatus::Ok => "the final response has an accepted code (2xx, or listed in --accept / --accept-for)".to_string(),
+        LinkStatus::Redirect { code, .. } => {
+            format!("HTTP {} is a redirect without a Location header that can be followed", code)
+        }
+        LinkStatus::Broken { code } => not_accepted(code, "and 404 / 410 mean the page is gone"),
+        LinkStatus::Unauthorized { code } => not_accepted(code, "and 401 means credentials are needed"),
+        LinkStatus::Forbidden { code } => not_accepted(code, "and 403 means access is refused"),
+        LinkStatus::RateLimited { code } => not_accepted(code, "and 429 means too many requests"),
+        LinkStatus::ClientError { code } => not_accepted(code, "and other 4xx codes are client errors"),
+        LinkStatus::ServerError { code } => not_accepted(code, "and 5xx codes are server errors"),
+        LinkStatus::UnexpectedStatus { code } => not_accepted(code, "and it is outside 200-599"),
+        LinkStatus::InvalidUrl => "reqwest could not build a request for this URL".to_string(),
+        LinkStatus::Timeout => format!(
+            "no connection within {:?} or no answer within {:?} (--connect-timeout, --timeout)",
+            settings.connect_timeout, settings.timeout
+        ),
+        LinkStatus::TooManyRedirects => format!("more than {} redirects (--max-redirects)", settings.max_redirects),
+        LinkStatus::ConnectionRefused => "the operating system reported ConnectionRefused".to_string(),
+        LinkStatus::ConnectionReset => "the operating system reported ConnectionReset or ConnectionAborted".to_string(),
+        LinkStatus::SslError => "connecting failed with InvalidData, which is how rustls reports TLS problems".to_string(),
+        LinkStatus::DnsError => "connecting failed and the host name does not resolve".to_string(),
+        LinkStatus::Error => "none of the other rules matched".to_string(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why `let _ = writeln!(out, ...)`?
+//    - writeln! on a String returns a fmt::Result, like writing to a file
+//    - Writing to memory can't fail, so `let _ =` says "ignore it on purpose"
+//
+// 2. What does tokio::time::timeout() return?
+//    - Ok(value) if the future finished in time, Err(Elapsed) if not
+//    - connect() itself returns a Result, hence Ok(Ok(stream)) and Ok(Err(e))
+//
+// 3. Why the nested `if let Some(...)`?
+//    - Each step needs the previous one: no address, no connection; no
+//      connection, no TLS. The redirect and GET steps always run, because
+//      they show how reqwest itself fails
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::RequestHeaders;
+
+    #[tokio::test]
+    async fn test_explain_invalid_url() {
+        let explanation = explain("not a url", &CheckOptions::default()).await;
+        assert_eq!(explanation.result.status, LinkStatus::InvalidUrl);
+        assert!(explanation.text.contains("Not a valid URL"));
+        assert!(explanation.text.contains("Rule: reqwest could not build a request"));
+    }
+
+    #[tokio::test]
+    async fn test_explain_refused_connection() {
+        // Bind a port, then free it, so nothing is listening there
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let explanation = explain(&format!("http://127.0.0.1:{}/", port), &CheckOptions::default()).await;
+        assert_eq!(explanation.result.status, LinkStatus::ConnectionRefused);
+        assert!(explanation.text.contains("127.0.0.1"));
+        assert!(explanation.text.contains("ConnectionRefused"));
+    }
+
+    // Answers every request on `ip` with `response`, returns the server's URL
+    fn serve(ip: &str, response: String) -> String {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind((ip, 0)).unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let _ = stream.read(&mut [0; 4096]);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_explain_stops_for_per_domain_headers() {
+        let target = serve("127.0.0.2", "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string());
+        let url = serve(
+            "127.0.0.1",
+            format!("HTTP/1.1 302 Found\r\nLocation: {}\r\nConnection: close\r\n\r\n", target),
+        );
+        let options = CheckOptions {
+            headers: RequestHeaders {
+                headers_for: vec!["127.0.0.1=X-Api-Key: secret".parse().unwrap()],
+                ..RequestHeaders::default()
+            },
+            ..CheckOptions::default()
+        };
+
+        let explanation = explain(&url, &options).await;
+        assert_eq!(explanation.result.status, LinkStatus::Ok);
+        assert!(explanation.text.contains("Stopped: per-domain headers are for another host"));
+        assert!(explanation.text.contains("Rule: stopped: per-domain headers are for another host"));
+        assert!(explanation.text.contains("Same status as HEAD"));
+    }
+}
@@ -0,0 +1,295 @@
+// src/checker/headers.rs
+// =============================================================================
+// This module adds custom headers, cookies and credentials to requests.
+//
+// Links into an internal wiki or staging docs answer 401/403 unless the
+// request carries a token or a session cookie. These flags add them, to the
+// link checks and to the pages the crawler downloads:
+//
+//   --header "Accept-Language: en"                  every request
+//   --header-for docs.example.com="X-Team: docs"    one domain
+//   --cookie consent=yes                            every request
+//   --cookie-for wiki.example.com=session=abc123    one domain
+//   --basic-auth-for staging.example.com=ci:env:STAGING_PASSWORD
+//   --bearer-for wiki.example.com=env:WIKI_TOKEN
+//
+// A value starting with `env:` is read from that environment variable, so
+// secrets can stay out of scripts and CI logs. Credentials are only offered
+// per domain: sending a token to every linked site would leak it. For the
+// same reason per-domain values don't follow a redirect to a host they
+// aren't meant for (see follows_to): that host gets a new request, with its
+// own headers.
+//
+// Domains match their subdomains too. When several credentials match, the
+// most specific domain wins; headers and cookies from every match are sent.
+//
+// Rust concepts:
+// - Newtype pattern: Secret wraps a String to change how it is printed
+// - Implementing Debug by hand: hiding a value instead of deriving
+// =============================================================================
+
+use reqwest::header::{HeaderName, HeaderValue, COOKIE};
+use reqwest::RequestBuilder;
+use std::fmt;
+use std::str::FromStr;
+
+use super::policy::ForDomain;
+
+// A value from the command line, or from an environment variable
+//
+// "env:WIKI_TOKEN" reads $WIKI_TOKEN; anything else is used as it is.
+// Debug output never shows the value, so it can't end up in a log.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    // The actual value, for putting it into a request
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Secret(***)")
+    }
+}
+
+impl FromStr for Secret {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.strip_prefix("env:") {
+            Some(variable) => std::env::var(variable)
+                .map(Secret)
+                .map_err(|_| format!("environment variable {} is not set", variable)),
+            None => Ok(Secret(text.to_string())),
+        }
+    }
+}
+
+// An extra request header, parsed from "NAME: VALUE"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub name: HeaderName,
+    pub value: Secret,
+}
+
+impl FromStr for Header {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (name, value) = text
+            .split_once(':')
+            .ok_or_else(|| format!("expected NAME: VALUE, got '{}'", text))?;
+        let name = HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|_| format!("'{}' is not a valid header name", name.trim()))?;
+        let value: Secret = value.trim().parse()?;
+        HeaderValue::from_str(value.expose())
+            .map_err(|_| format!("the value of header {} contains invalid characters", name))?;
+        Ok(Header { name, value })
+    }
+}
+
+// A cookie, parsed from "NAME=VALUE"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    pub name: String,
+    pub value: Secret,
+}
+
+impl FromStr for Cookie {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (name, value) = text
+            .split_once('=')
+            .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", text))?;
+        let cookie = Cookie {
+            name: name.trim().to_string(),
+            value: value.trim().parse()?,
+        };
+        if cookie.name.is_empty() || HeaderValue::from_str(&cookie.pair()).is_err() {
+            return Err(format!("'{}' is not a valid cookie", cookie.name));
+        }
+        Ok(cookie)
+    }
+}
+
+impl Cookie {
+    // "name=value", as it appears in the Cookie header
+    fn pair(&self) -> String {
+        format!("{}={}", self.name, self.value.expose())
+    }
+}
+
+// Credentials for one domain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Credentials {
+    /// Authorization: Basic ..., parsed from "USER:PASSWORD"
+    Basic { user: String, password: Secret },
+    /// Authorization: Bearer TOKEN
+    Bearer(Secret),
+}
+
+impl Credentials {
+    // Parses "USER:PASSWORD" (the password may be env:NAME)
+    pub fn basic(text: &str) -> Result<Self, String> {
+        let (user, password) = text
+            .split_once(':')
+            .ok_or_else(|| format!("expected USER:PASSWORD, got '{}'", text))?;
+        Ok(Credentials::Basic {
+            user: user.to_string(),
+            password: password.parse()?,
+        })
+    }
+}
+
+// Everything extra that requests carry
+//
+// Default::default() adds nothing.
+#[derive(Debug, Clone, Default)]
+pub struct RequestHeaders {
+    /// Headers for every request (--header)
+    pub headers: Vec<Header>,
+    /// Headers for one domain (--header-for)
+    pub headers_for: Vec<ForDomain<Header>>,
+    /// Cookies for every request (--cookie)
+    pub cookies: Vec<Cookie>,
+    /// Cookies for one domain (--cookie-for)
+    pub cookies_for: Vec<ForDomain<Cookie>>,
+    /// Basic or bearer authentication per domain; the most specific wins
+    pub credentials: Vec<ForDomain<Credentials>>,
+}
+
+impl RequestHeaders {
+    // Adds the headers, cookies and credentials that apply to `url`
+    //
+    // Example:
+    //   let request = options.headers.apply(&url, client.head(&url));
+    pub fn apply(&self, url: &str, mut request: RequestBuilder) -> RequestBuilder {
+        let domain_headers = ForDomain::matching(&self.headers_for, url).map(|entry| &entry.value);
+        for header in self.headers.iter().chain(domain_headers) {
+            // Values were checked when parsing, so this never fails
+            if let Ok(mut value) = HeaderValue::from_str(header.value.expose()) {
+                value.set_sensitive(true);
+                request = request.header(header.name.clone(), value);
+            }
+        }
+
+        // All cookies go into one Cookie header: "a=1; b=2"
+        let domain_cookies = ForDomain::matching(&self.cookies_for, url).map(|entry| &entry.value);
+        let cookies: Vec<String> = self.cookies.iter().chain(domain_cookies).map(Cookie::pair).collect();
+        if !cookies.is_empty() {
+            if let Ok(mut value) = HeaderValue::from_str(&cookies.join("; ")) {
+                value.set_sensitive(true);
+                request = request.header(COOKIE, value);
+            }
+        }
+
+        // reqwest marks these Authorization headers as sensitive for us
+        match ForDomain::most_specific(&self.credentials, url) {
+            Some(Credentials::Basic { user, password }) => request.basic_auth(user, Some(password.expose())),
+            Some(Credentials::Bearer(token)) => request.bearer_auth(token.expose()),
+            None => request,
+        }
+    }
+
+    // Returns true if a request for `from` may be redirected to `to`
+    //
+    // Headers set on a request are sent again on every redirect, and reqwest
+    // only strips Authorization and Cookie when the host changes. So a
+    // redirect is only safe if every per-domain header, cookie and credential
+    // that applies to `from` applies to `to` as well.
+    pub fn follows_to(&self, from: &str, to: &str) -> bool {
+        let safe = |applies_to_from: bool, applies_to_to: bool| !applies_to_from || applies_to_to;
+        self.headers_for.iter().all(|e| safe(e.applies_to(from), e.applies_to(to)))
+            && self.cookies_for.iter().all(|e| safe(e.applies_to(from), e.applies_to(to)))
+            && self.credentials.iter().all(|e| safe(e.applies_to(from), e.applies_to(to)))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. What is the newtype pattern?
+//    - struct Secret(String) is a new type that only wraps a String
+//    - It costs nothing at runtime, but lets us give it its own behaviour:
+//      here a Debug that prints *** instead of the value
+//
+// 2. Why read secrets from environment variables?
+//    - Command lines show up in `ps`, shell history and CI logs
+//    - CI systems store secrets as environment variables anyway
+//
+// 3. What does set_sensitive(true) do?
+//    - It tells the HTTP library the header holds a secret, so it isn't
+//      shown when the request is printed for debugging
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_values() {
+        std::env::set_var("LINK_GUARDIAN_TEST_TOKEN", "s3cret");
+        let token: Secret = "env:LINK_GUARDIAN_TEST_TOKEN".parse().unwrap();
+        assert_eq!(token.expose(), "s3cret");
+        assert_eq!(format!("{:?}", token), "Secret(***)");
+        assert!("env:LINK_GUARDIAN_TEST_MISSING".parse::<Secret>().is_err());
+
+        let header: Header = "X-Team:  docs".parse().unwrap();
+        assert_eq!(header.name, "x-team");
+        assert_eq!(header.value.expose(), "docs");
+        assert!("no colon".parse::<Header>().is_err());
+        assert!("Bad Name: x".parse::<Header>().is_err());
+
+        let basic = Credentials::basic("ci:env:LINK_GUARDIAN_TEST_TOKEN").unwrap();
+        assert_eq!(basic, Credentials::Basic { user: "ci".to_string(), password: token });
+
+        let cookie: ForDomain<Cookie> = "wiki.example.com=session=a=b".parse().unwrap();
+        assert_eq!(cookie.domain, "wiki.example.com");
+        assert_eq!(cookie.value.pair(), "session=a=b");
+    }
+
+    #[test]
+    fn test_apply_per_domain() {
+        let headers = RequestHeaders {
+            headers: vec!["Accept-Language: en".parse().unwrap()],
+            headers_for: vec!["docs.example.com=X-Team: docs".parse().unwrap()],
+            cookies: vec!["consent=yes".parse().unwrap()],
+            cookies_for: vec!["example.com=session=abc".parse().unwrap()],
+            credentials: vec![ForDomain {
+                domain: "docs.example.com".to_string(),
+                value: Credentials::Bearer("token".parse().unwrap()),
+            }],
+        };
+        let client = reqwest::Client::new();
+        let build = |url: &str| headers.apply(url, client.get(url)).build().unwrap();
+
+        let docs = build("https://docs.example.com/page");
+        assert_eq!(docs.headers()["accept-language"], "en");
+        assert_eq!(docs.headers()["x-team"], "docs");
+        assert_eq!(docs.headers()["cookie"], "consent=yes; session=abc");
+        assert_eq!(docs.headers()["authorization"], "Bearer token");
+
+        let other = build("https://other.org/");
+        assert_eq!(other.headers()["cookie"], "consent=yes");
+        assert!(other.headers().get("x-team").is_none());
+        assert!(other.headers().get("authorization").is_none());
+        assert!(docs.headers()["x-team"].is_sensitive());
+    }
+
+    #[test]
+    fn test_follows_to() {
+        let headers = RequestHeaders {
+            headers: vec!["Accept-Language: en".parse().unwrap()],
+            headers_for: vec!["example.com=X-Api-Key: key".parse().unwrap()],
+            ..RequestHeaders::default()
+        };
+        assert!(headers.follows_to("https://example.com/a", "https://docs.example.com/b"));
+        assert!(!headers.follows_to("https://example.com/a", "https://evil.org/"));
+        assert!(headers.follows_to("https://other.org/a", "https://evil.org/"));
+        assert!(RequestHeaders::default().follows_to("https://example.com/", "https://evil.org/"));
+    }
+}
@@ -19,6 +19,9 @@
 
 use scraper::{Html, Selector};
 use url::Url;
+use tracing::warn;
+
+use super::css::extract_css_links;
 
 // Extracts all links from HTML content
 //
@@ -28,6 +31,10 @@ use url::Url;
 //
 // Returns: Vec<String> containing all absolute URLs found
 //
+// Besides <a href> links this also returns:
+//   - stylesheets: <link rel="stylesheet" href="...">
+//   - url() references in <style> blocks and inline style="..." attributes
+//
 // Example:
 //   html = "<a href='/docs'>Docs</a>"
 //   base_url = "https://example.com"
@@ -49,7 +56,7 @@ pub fn extract_html_links(html: &str, base_url: &str) -> Vec<String> {
         Ok(url) => url,
         Err(_) => {
             // If base URL is invalid, we can't resolve relative links
-            eprintln!("Warning: Invalid base URL: {}", base_url);
+            warn!("Invalid base URL: {}", base_url);
             return links;
         }
     };
@@ -68,9 +75,58 @@ pub fn extract_html_links(html: &str, base_url: &str) -> Vec<String> {
         }
     }
 
+    // Stylesheets are resources too - a missing one breaks the whole page
+    links.extend(stylesheet_links(&document, &base));
+
+    // url(...) references in <style> blocks and style="..." attributes
+    // Relative URLs in inline CSS are resolved against the page itself
+    let style_blocks = Selector::parse("style").unwrap();
+    for element in document.select(&style_blocks) {
+        let css: String = element.text().collect();
+        links.extend(extract_css_links(&css, base.as_str()));
+    }
+
+    let styled = Selector::parse("[style]").unwrap();
+    for element in document.select(&styled) {
+        if let Some(css) = element.value().attr("style") {
+            links.extend(extract_css_links(css, base.as_str()));
+        }
+    }
+
     links
 }
 
+// Extracts the URLs of external stylesheets used by a page
+//
+// The crawler uses this to download same-site stylesheets and check the
+// fonts and images they reference.
+//
+// Example:
+//   html = "<link rel='stylesheet' href='/css/site.css'>"
+//   base_url = "https://example.com/page"
+//   result = ["https://example.com/css/site.css"]
+pub fn extract_stylesheet_links(html: &str, base_url: &str) -> Vec<String> {
+    let document = Html::parse_document(html);
+    match Url::parse(base_url) {
+        Ok(base) => stylesheet_links(&document, &base),
+        Err(_) => Vec::new(),
+    }
+}
+
+// Finds <link rel="stylesheet" href="..."> in an already-parsed document
+fn stylesheet_links(document: &Html, base: &Url) -> Vec<String> {
+    // rel is a space-separated list, e.g. rel="preload stylesheet",
+    // and ~= matches one word in such a list
+    let selector = Selector::parse("link[rel~=stylesheet][href]").unwrap();
+
+    document
+        .select(&selector)
+        .filter_map(|element| element.value().attr("href"))
+        .filter_map(|href| resolve_url(base, href))
+        .filter(|url| is_checkable_link(url))
+        .collect()
+}
+
 // Resolves a possibly-relative URL to an absolute URL
 //
 // Parameters:
@@ -184,4 +240,26 @@ mod tests {
         let links = extract_html_links(html, "https://example.com/page/");
         assert_eq!(links.len(), 3);
     }
+
+    #[test]
+    fn test_stylesheets_and_inline_styles() {
+        let html = r#"
+            <link rel="stylesheet" href="/css/site.css">
+            <style>.hero { background: url(/img/hero.png); }</style>
+            <div style="background-image: url('banner.jpg')"></div>
+        "#;
+        let links = extract_html_links(html, "https://example.com/page/");
+        assert_eq!(
+            links,
+            vec![
+                "https://example.com/css/site.css",
+                "https://example.com/img/hero.png",
+                "https://example.com/page/banner.jpg",
+            ]
+        );
+        assert_eq!(
+            extract_stylesheet_links(html, "https://example.com/page/"),
+            vec!["https://example.com/css/site.css"]
+        );
+    }
 }
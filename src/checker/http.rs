@@ -4,8 +4,11 @@
 //
 // Key functionality:
 // - Makes HTTP HEAD requests (lightweight, no body download)
-// - Falls back to GET if HEAD fails
-// - Detects various failure modes (404, timeout, SSL errors, etc.)
+// - Asks "has this changed?" (If-None-Match) for expired cached results
+// - Decides on the HEAD response alone; there is no GET retry (`explain`
+//   sends a GET too, to show servers that answer HEAD differently)
+// - Detects various failure modes (404, 403, 5xx, timeout, refused
+//   connections, SSL errors, etc.)
 // - Runs checks concurrently with rate limiting
 //
 // Rust concepts:
@@ -15,24 +18,50 @@
 // - Streams: For processing many items concurrently
 // =============================================================================
 
-use reqwest::{Client, StatusCode};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::error::Error as StdError;
+use std::io;
+use std::time::Instant;
 use futures::stream::{self, StreamExt};  // StreamExt gives us .buffer_unordered()
+use tracing::{debug, info, instrument, trace};
+use url::Url;
+
+use super::client::{ClientSettings, Clients};
+use super::location::SourceLocation;
+use super::headers::RequestHeaders;
+use super::policy::AcceptPolicy;
+use crate::cache::{SharedCache, Validators};
 
 // Represents the status of a link after checking
 //
 // #[derive(Serialize, Deserialize)] lets us convert to/from JSON
 // #[derive(Debug, Clone)] enables debugging and cloning
-#[derive(Debug, Clone, Serialize, Deserialize)]
+//
+// Statuses that come from an HTTP response carry the status code, so
+// reports can show exactly what the server answered:
+//   {"url": "...", "status": "server_error", "code": 503, ...}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "status", rename_all = "snake_case")]
 pub enum LinkStatus {
-    /// Link is working (200 OK)
+    /// Link is working (2xx, or a code accepted with --accept)
     Ok,
     /// Link redirects to another URL (301, 302, etc.)
-    Redirect(String),  // Holds the redirect target URL
-    /// Link is broken (404, 410, etc.)
-    Broken,
+    Redirect { code: u16, target: String },
+    /// Link is gone (404 Not Found, 410 Gone)
+    Broken { code: u16 },
+    /// The server wants credentials (401)
+    Unauthorized { code: u16 },
+    /// The server refuses access (403), common for sites behind a login
+    Forbidden { code: u16 },
+    /// Too many requests (429) - try a lower --concurrency
+    RateLimited { code: u16 },
+    /// Any other 4xx response
+    ClientError { code: u16 },
+    /// The server failed (5xx), often temporary
+    ServerError { code: u16 },
+    /// A code outside the usual ranges (1xx, or above 599)
+    UnexpectedStatus { code: u16 },
     /// Request timed out
     Timeout,
     /// SSL/TLS certificate error
@@ -41,10 +70,96 @@ pub enum LinkStatus {
     TooManyRedirects,
     /// Could not resolve hostname
     DnsError,
+    /// Nothing is listening on the host and port
+    ConnectionRefused,
+    /// The server closed the connection before answering
+    ConnectionReset,
+    /// The URL can't be requested (malformed, or not http/https)
+    InvalidUrl,
     /// Other error
     Error,
 }
 
+impl LinkStatus {
+    /// Every status name, in the order reports list them
+    pub const NAMES: &'static [&'static str] = &[
+        "ok",
+        "redirect",
+        "broken",
+        "unauthorized",
+        "forbidden",
+        "rate_limited",
+        "client_error",
+        "server_error",
+        "unexpected_status",
+        "dns_error",
+        "connection_refused",
+        "connection_reset",
+        "ssl_error",
+        "too_many_redirects",
+        "timeout",
+        "invalid_url",
+        "error",
+    ];
+
+    /// Short machine-readable name, the same as the "status" field in JSON
+    ///
+    /// Example: LinkStatus::DnsError.name() == "dns_error"
+    pub fn name(&self) -> &'static str {
+        match self {
+            LinkStatus::Ok => "ok",
+            LinkStatus::Redirect { .. } => "redirect",
+            LinkStatus::Broken { .. } => "broken",
+            LinkStatus::Unauthorized { .. } => "unauthorized",
+            LinkStatus::Forbidden { .. } => "forbidden",
+            LinkStatus::RateLimited { .. } => "rate_limited",
+            LinkStatus::ClientError { .. } => "client_error",
+            LinkStatus::ServerError { .. } => "server_error",
+            LinkStatus::UnexpectedStatus { .. } => "unexpected_status",
+            LinkStatus::Timeout => "timeout",
+            LinkStatus::SslError => "ssl_error",
+            LinkStatus::TooManyRedirects => "too_many_redirects",
+            LinkStatus::DnsError => "dns_error",
+            LinkStatus::ConnectionRefused => "connection_refused",
+            LinkStatus::ConnectionReset => "connection_reset",
+            LinkStatus::InvalidUrl => "invalid_url",
+            LinkStatus::Error => "error",
+        }
+    }
+
+    /// The HTTP status code, for statuses that come from a response
+    ///
+    /// Example: LinkStatus::Forbidden { code: 403 }.code() == Some(403)
+    pub fn code(&self) -> Option<u16> {
+        match self {
+            LinkStatus::Redirect { code, .. }
+            | LinkStatus::Broken { code }
+            | LinkStatus::Unauthorized { code }
+            | LinkStatus::Forbidden { code }
+            | LinkStatus::RateLimited { code }
+            | LinkStatus::ClientError { code }
+            | LinkStatus::ServerError { code }
+            | LinkStatus::UnexpectedStatus { code } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// The status for a response code that isn't accepted or a redirect
+    ///
+    /// Example: LinkStatus::from_code(503) == LinkStatus::ServerError { code: 503 }
+    pub fn from_code(code: u16) -> LinkStatus {
+        match code {
+            404 | 410 => LinkStatus::Broken { code },
+            401 => LinkStatus::Unauthorized { code },
+            403 => LinkStatus::Forbidden { code },
+            429 => LinkStatus::RateLimited { code },
+            400..=499 => LinkStatus::ClientError { code },
+            500..=599 => LinkStatus::ServerError { code },
+            _ => LinkStatus::UnexpectedStatus { code },
+        }
+    }
+}
+
 // Represents the result of checking a single link
 //
 // This struct holds all information about a link check
@@ -58,6 +173,11 @@ pub struct LinkCheckResult {
     /// Optional message with more details
     #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
+    /// Where the link was found (files, lines, pages)
+    ///
+    /// Filled in after checking, see LinkCollection::attach_locations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<SourceLocation>,
 }
 
 impl LinkCheckResult {
@@ -65,10 +185,38 @@ impl LinkCheckResult {
     ///
     /// Returns true for Ok and Redirect statuses
     pub fn is_ok(&self) -> bool {
-        matches!(self.status, LinkStatus::Ok | LinkStatus::Redirect(_))
+        matches!(self.status, LinkStatus::Ok | LinkStatus::Redirect { .. })
     }
 }
 
+// Builds a result by hand for the tests of the report, cache and baseline modules
+#[cfg(test)]
+impl LinkCheckResult {
+    pub fn new(url: &str, status: LinkStatus, message: Option<&str>, locations: Vec<SourceLocation>) -> Self {
+        LinkCheckResult {
+            url: url.to_string(),
+            status,
+            message: message.map(str::to_string),
+            locations,
+        }
+    }
+}
+
+// Settings for checking links
+//
+// Default::default() gives the built-in behaviour (only 2xx is OK).
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    /// Which status codes count as OK, globally and per domain
+    pub accept: AcceptPolicy,
+    /// Results of earlier runs to reuse (--cache), None = always check
+    pub cache: Option<SharedCache>,
+    /// Extra headers, cookies and credentials (--header, --bearer-for, ...)
+    pub headers: RequestHeaders,
+    /// User-Agent, timeouts and redirect limit, globally and per domain
+    pub client: ClientSettings,
+}
+
 // Checks multiple links concurrently
 //
 // This is the main entry point for link checking.
@@ -83,21 +231,64 @@ impl LinkCheckResult {
 // - Each HTTP request takes time (network latency)
 // - Running them concurrently is MUCH faster than sequential
 // - Example: 1000 links * 1 sec each = 1000 sec sequential vs ~5 sec concurrent
+//
+// The CLI uses check_links_with() to report progress as results arrive;
+// this simpler version is kept for tests and library-style use.
+#[allow(dead_code)]
 pub async fn check_links(urls: Vec<String>, concurrency: usize) -> Vec<LinkCheckResult> {
-    // Create an HTTP client with reasonable settings
-    // We'll reuse this client for all requests (connection pooling)
-    let client = Client::builder()
-        .timeout(Duration::from_secs(10))  // 10 second timeout per request
-        .redirect(reqwest::redirect::Policy::limited(5))  // Follow up to 5 redirects
-        .build()
-        .expect("Failed to create HTTP client");
+    check_links_with(urls, concurrency, &CheckOptions::default(), |_| {}).await
+}
+
+// Checks multiple links concurrently, calling `on_result` as each one finishes
+//
+// This lets callers stream results (e.g. NDJSON output) instead of waiting
+// for the whole batch. Results arrive in completion order, not input order.
+//
+// Parameters:
+//   urls: Vector of URLs to check
+//   concurrency: How many links to check at once
+//   options: Accepted status codes, the result cache and other settings
+//   on_result: Called once per link, as soon as its check completes
+//              (cached results first, before any request is made)
+pub async fn check_links_with<F>(
+    urls: Vec<String>,
+    concurrency: usize,
+    options: &CheckOptions,
+    mut on_result: F,
+) -> Vec<LinkCheckResult>
+where
+    F: FnMut(&LinkCheckResult),
+{
+    // Clients are reused for all requests (connection pooling); there's
+    // more than one only if some domains have their own timeouts etc.
+    let clients = Clients::new(options.client.clone(), options.headers.clone());
+
+    let total = urls.len();
+    let mut results = Vec::with_capacity(total);
+
+    // Answer what we can from the cache; only the rest goes over the network
+    let mut to_check = Vec::with_capacity(total);
+    for url in urls {
+        let cached = options.cache.as_ref().and_then(|cache| cache.lock().unwrap().get(&url, &options.accept));
+        match cached {
+            Some(result) => {
+                debug!(url = %url, "using cached result: {}", result.status.name());
+                on_result(&result);
+                results.push(result);
+            }
+            None => to_check.push(url),
+        }
+    }
+    if !results.is_empty() {
+        info!("💾 {} link(s) answered from the cache", results.len());
+    }
 
     // Create a stream of futures
     // Each future represents checking one URL
-    let futures = urls.into_iter().map(|url| {
-        let client = client.clone();  // Clone the client for each task
+    let futures = to_check.into_iter().map(|url| {
+        let client = clients.for_url(&url);  // The client for this URL's domain
         async move {
-            check_single_link(client, url).await
+            check_single_link(client, url, options).await
         }
     });
 
@@ -109,10 +300,15 @@ pub async fn check_links(urls: Vec<String>, concurrency: usize) -> Vec<LinkCheck
     // - Too low: slow checking (sequential-like performance)
     // - Too high: might overwhelm the network or get rate-limited by servers
     // - Default 500: Fast for most use cases without triggering rate limits
-    stream::iter(futures)
-        .buffer_unordered(concurrency)
-        .collect()  // Collect all results into a Vec
-        .await
+    let mut checks = stream::iter(futures).buffer_unordered(concurrency);
+
+    // Pull results out of the stream one at a time as they complete
+    while let Some(result) = checks.next().await {
+        on_result(&result);
+        results.push(result);
+    }
+
+    results
 }
 
 // Checks a single link
@@ -122,23 +318,138 @@ pub async fn check_links(urls: Vec<String>, concurrency: usize) -> Vec<LinkCheck
 // Parameters:
 //   client: reqwest HTTP client (borrowed, we don't own it)
 //   url: the URL to check (owned String)
+//   options: which status codes are accepted, etc.
 //
 // Returns: LinkCheckResult with status and details
-async fn check_single_link(client: Client, url: String) -> LinkCheckResult {
-    // First, try a HEAD request (faster, no body download)
-    let result = client.head(&url).send().await;
-
-    // Match on the result to handle success and various error types
-    match result {
-        Ok(response) => {
-            // Got a response! Check the status code
-            analyze_response(url, response)
+//
+// #[instrument] wraps the function in a "check" span, so every log line
+// written while checking is tagged with the URL.
+#[instrument(name = "check", skip_all, fields(url = %url))]
+pub(super) async fn check_single_link(client: Client, url: String, options: &CheckOptions) -> LinkCheckResult {
+    let started = Instant::now();
+
+    // An expired cached result with an ETag or Last-Modified lets us ask
+    // "has this changed?" instead of checking from scratch
+    let mut validators = options.cache.as_ref().and_then(|cache| cache.lock().unwrap().validators(&url, &options.accept));
+
+    // Runs at most twice: again without validators if a 304 can't be matched
+    // with a cache entry any more
+    let checked = loop {
+        // First, try a HEAD request (faster, no body download)
+        let head = |target: &str| {
+            let request = options.headers.apply(target, client.head(target));
+            match &validators {
+                Some(validators) if target == url => validators.apply(request),
+                _ => request,
+            }
+        };
+        let result = send_following(&url, options, head).await;
+        let elapsed_ms = started.elapsed().as_millis();
+
+        // Match on the result to handle success and various error types
+        match result {
+            Ok(None) => {
+                debug!(elapsed_ms, "too many redirects to hosts without the same headers");
+                let result = LinkCheckResult {
+                    url,
+                    status: LinkStatus::TooManyRedirects,
+                    message: Some("Too many redirects".to_string()),
+                    locations: Vec::new(),
+                };
+                remember(options, &result, None, Validators::default());
+                break result;
+            }
+            Ok(Some(response)) => {
+                // Got a response! Check the status code
+                debug!(elapsed_ms, "HEAD -> {} in {} ms", response.status(), elapsed_ms);
+                trace!(headers = ?response.headers(), "response headers");
+
+                // 304 Not Modified: the cached result still holds
+                if validators.is_some() && response.status() == StatusCode::NOT_MODIFIED {
+                    let cached = options.cache.as_ref().and_then(|cache| cache.lock().unwrap().revalidate(&url));
+                    match cached {
+                        Some(result) => break result,
+                        None => {
+                            // The entry was removed (e.g. by another check of
+                            // the same URL) after we read its validators
+                            debug!("304 Not Modified but no cached result any more, checking again");
+                            validators = None;
+                            continue;
+                        }
+                    }
+                }
+
+                let code = response.status().as_u16();
+                let new_validators = Validators::from_headers(response.headers());
+                let result = analyze_response(url, response, &options.accept);
+                remember(options, &result, Some(code), new_validators);
+                break result;
+            }
+            Err(e) => {
+                // Request failed - figure out why
+                // {:?} shows the whole chain of causes, which the short
+                // message in the report leaves out
+                debug!(elapsed_ms, "HEAD failed after {} ms: {:?}", elapsed_ms, e);
+                let result = categorize_error(url, e).await;
+                remember(options, &result, None, Validators::default());
+                break result;
+            }
         }
-        Err(e) => {
-            // Request failed - figure out why
-            categorize_error(url, e)
+    };
+
+    debug!(status = checked.status.name(), "classified as {}", checked.status.name());
+    checked
+}
+
+// Sends the request `build(url)` makes, continuing redirects the client
+// stopped because per-domain headers were only meant for the first host
+//
+// The client never carries --header-for values etc. to another host (see
+// RequestHeaders::follows_to), so the next host gets a request of its own,
+// with its own headers, and its answer is the one that counts.
+//
+// Returns Ok(None) after more than max_redirects of these new requests.
+//
+// Example:
+//   let response = send_following(&url, options, |target| client.get(target)).await?;
+pub async fn send_following<F>(url: &str, options: &CheckOptions, build: F) -> reqwest::Result<Option<Response>>
+where
+    F: Fn(&str) -> RequestBuilder,
+{
+    let max_redirects = options.client.for_url(url).max_redirects;
+    let mut target = url.to_string();
+
+    for _ in 0..=max_redirects {
+        let response = build(&target).send().await?;
+        match stopped_redirect(&target, &response, &options.headers) {
+            Some(next) => {
+                debug!("redirect to {} not followed with the headers for {}, sending a new request", next, target);
+                target = next;
+            }
+            None => return Ok(Some(response)),
         }
     }
+    Ok(None)
+}
+
+// Where `response` redirects to, if the client stopped there to keep the
+// per-domain headers of a request for `requested` away from that host
+pub(super) fn stopped_redirect(requested: &str, response: &Response, headers: &RequestHeaders) -> Option<String> {
+    if !response.status().is_redirection() {
+        return None;
+    }
+    let location = response.headers().get("location")?.to_str().ok()?;
+    let next = response.url().join(location).ok()?.to_string();
+    (!headers.follows_to(requested, &next)).then_some(next)
+}
+
+// Stores a new result in the cache (if --cache is on)
+//
+// `code` is the HTTP status code of the response, if there was one.
+fn remember(options: &CheckOptions, result: &LinkCheckResult, code: Option<u16>, validators: Validators) {
+    if let Some(cache) = &options.cache {
+        cache.lock().unwrap().insert(result, code, validators);
+    }
 }
 
 // Analyzes an HTTP response to determine link status
@@ -146,84 +457,155 @@ async fn check_single_link(client: Client, url: String) -> LinkCheckResult {
 // HTTP status codes:
 // - 200-299: Success
 // - 300-399: Redirect
-// - 400-499: Client error (404 not found, etc.)
+// - 400-499: Client error (404 not found, 403 forbidden, 429 rate limited, ...)
 // - 500-599: Server error
-fn analyze_response(url: String, response: reqwest::Response) -> LinkCheckResult {
+//
+// Codes listed in the accept policy (only 2xx by default) are always OK.
+// Everything else is classified by LinkStatus::from_code().
+fn analyze_response(url: String, response: reqwest::Response, accept: &AcceptPolicy) -> LinkCheckResult {
     let status_code = response.status();
+    let code = status_code.as_u16();
 
-    if status_code.is_success() {
-        // 2xx status codes mean success
-        LinkCheckResult {
-            url,
-            status: LinkStatus::Ok,
-            message: Some(format!("HTTP {}", status_code.as_u16())),
-        }
+    let (status, message) = if accept.accepts(&url, code) {
+        // Accepted codes mean success; say so when it isn't a plain 2xx
+        let message = if status_code.is_success() {
+            format!("HTTP {}", code)
+        } else {
+            format!("HTTP {} (accepted)", code)
+        };
+        (LinkStatus::Ok, message)
     } else if status_code.is_redirection() {
         // 3xx status codes mean redirect
         // Try to get the Location header to show where it redirects to
-        let redirect_target = response
+        let target = response
             .headers()
             .get("location")
             .and_then(|v| v.to_str().ok())
             .unwrap_or("unknown")
             .to_string();
 
-        LinkCheckResult {
-            url,
-            status: LinkStatus::Redirect(redirect_target.clone()),
-            message: Some(format!("HTTP {} -> {}", status_code.as_u16(), redirect_target)),
-        }
-    } else if matches!(status_code, StatusCode::NOT_FOUND | StatusCode::GONE) {
-        // 404 Not Found or 410 Gone - definitely broken
-        LinkCheckResult {
-            url,
-            status: LinkStatus::Broken,
-            message: Some(format!("HTTP {}", status_code.as_u16())),
-        }
+        let message = format!("HTTP {} -> {}", code, target);
+        (LinkStatus::Redirect { code, target }, message)
     } else {
-        // Other status codes (e.g., 500 server errors)
-        // We'll consider these as errors rather than definitively broken
-        LinkCheckResult {
-            url,
-            status: LinkStatus::Error,
-            message: Some(format!("HTTP {}", status_code.as_u16())),
-        }
+        // Include the reason phrase, e.g. "HTTP 503 Service Unavailable"
+        let message = match status_code.canonical_reason() {
+            Some(reason) => format!("HTTP {} {}", code, reason),
+            None => format!("HTTP {}", code),
+        };
+        (LinkStatus::from_code(code), message)
+    };
+
+    LinkCheckResult {
+        url,
+        status,
+        message: Some(message),
+        locations: Vec::new(),
     }
 }
 
 // Categorizes different error types from reqwest
 //
 // reqwest errors can happen for many reasons:
+// - The URL can't be requested at all
 // - Network timeout
-// - DNS resolution failure
+// - Connection refused or reset by the server
 // - SSL certificate issues
+// - DNS resolution failure
 // - Too many redirects
-// - etc.
-fn categorize_error(url: String, error: reqwest::Error) -> LinkCheckResult {
-    // Convert error to string once to avoid lifetime issues
-    let error_string = error.to_string();
+//
+// Instead of searching the error message for words like "dns", we look at
+// what reqwest tells us (is_timeout(), is_connect(), ...) and at the
+// std::io::Error underneath, whose ErrorKind says what the OS reported.
+async fn categorize_error(url: String, error: reqwest::Error) -> LinkCheckResult {
+    let io_kind = io_error_kind(&error);
 
-    let (status, message) = if error.is_timeout() {
-        (LinkStatus::Timeout, "Request timed out".to_string())
+    let status = if error.is_builder() {
+        // reqwest refused to build the request: bad URL or unsupported scheme
+        LinkStatus::InvalidUrl
+    } else if error.is_timeout() || io_kind == Some(io::ErrorKind::TimedOut) {
+        LinkStatus::Timeout
     } else if error.is_redirect() {
-        (LinkStatus::TooManyRedirects, "Too many redirects".to_string())
-    } else if error.is_connect() {
-        // Connection errors often mean DNS issues or host unreachable
-        if error_string.contains("dns") {
-            (LinkStatus::DnsError, "Could not resolve hostname".to_string())
-        } else {
-            (LinkStatus::Error, "Connection failed".to_string())
-        }
-    } else if error_string.contains("certificate") || error_string.contains("ssl") {
-        (LinkStatus::SslError, "SSL certificate error".to_string())
+        LinkStatus::TooManyRedirects
     } else {
-        (LinkStatus::Error, error_string.clone())
+        match io_kind {
+            Some(io::ErrorKind::ConnectionRefused) => LinkStatus::ConnectionRefused,
+            Some(io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionAborted) => {
+                LinkStatus::ConnectionReset
+            }
+            // rustls reports certificate problems as InvalidData while connecting
+            Some(io::ErrorKind::InvalidData) if error.is_connect() => LinkStatus::SslError,
+            // The resolver's errors have no useful ErrorKind, so ask it directly
+            _ if error.is_connect() && !host_resolves(&url).await => LinkStatus::DnsError,
+            _ => LinkStatus::Error,
+        }
+    };
+
+    let message = match status {
+        LinkStatus::InvalidUrl => format!("Invalid URL: {}", root_cause(&error)),
+        LinkStatus::Timeout => "Request timed out".to_string(),
+        LinkStatus::TooManyRedirects => "Too many redirects".to_string(),
+        LinkStatus::ConnectionRefused => "Connection refused".to_string(),
+        LinkStatus::ConnectionReset => "Connection reset by server".to_string(),
+        LinkStatus::SslError => format!("SSL certificate error: {}", root_cause(&error)),
+        LinkStatus::DnsError => "Could not resolve hostname".to_string(),
+        _ if error.is_connect() => format!("Connection failed: {}", root_cause(&error)),
+        _ => error.to_string(),
     };
 
     LinkCheckResult {
         url,
         status,
         message: Some(message),
+        locations: Vec::new(),
+    }
+}
+
+// The kind of the first std::io::Error in the error's chain of causes
+//
+// reqwest wraps hyper's error, which wraps the io::Error from the socket.
+// An io::Error can wrap another one (hyper-rustls wraps rustls' InvalidData
+// error in one of kind Other); source() skips the inner one, so get_ref()
+// is used to reach it.
+fn io_error_kind(error: &reqwest::Error) -> Option<io::ErrorKind> {
+    let mut source = error.source();
+    while let Some(cause) = source {
+        if let Some(mut io_error) = cause.downcast_ref::<io::Error>() {
+            while let Some(inner) = io_error.get_ref().and_then(|e| e.downcast_ref::<io::Error>()) {
+                io_error = inner;
+            }
+            return Some(io_error.kind());
+        }
+        source = cause.source();
+    }
+    None
+}
+
+// The innermost cause's message, e.g. "invalid peer certificate: Expired"
+pub(super) fn root_cause(error: &(dyn StdError + 'static)) -> String {
+    let mut cause = error;
+    while let Some(next) = cause.source() {
+        cause = next;
+    }
+    cause.to_string()
+}
+
+// Returns true if the URL's host resolves to at least one address
+//
+// Hosts we can't even extract from the URL count as resolving, so the
+// error is reported as it is rather than as a DNS problem.
+async fn host_resolves(url: &str) -> bool {
+    let parsed = match Url::parse(url) {
+        Ok(parsed) => parsed,
+        Err(_) => return true,
+    };
+    let (host, port) = match (parsed.host_str(), parsed.port_or_known_default()) {
+        (Some(host), Some(port)) => (host.to_string(), port),
+        _ => return true,
+    };
+
+    match tokio::net::lookup_host((host, port)).await {
+        Ok(mut addresses) => addresses.next().is_some(),
+        Err(_) => false,
     }
 }
 
@@ -261,11 +643,36 @@ fn categorize_error(url: String, error: reqwest::Error) -> LinkCheckResult {
 //    - Waits for an async operation to complete
 //    - Yields control to other tasks while waiting
 //    - Only works inside async functions
+//
+// 7. What is error.source()?
+//    - Errors can wrap other errors: reqwest -> hyper -> std::io::Error
+//    - source() returns the wrapped error, so a loop walks down the chain
+//    - downcast_ref::<io::Error>() asks "is this cause an io::Error?"
 // -----------------------------------------------------------------------------
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cache::{CacheTtls, ResultCache};
+    use std::io::{Read, Write};
+    use std::sync::{Arc, Mutex};
+
+    // Answers every request on a local port of `ip` with `respond(request)`
+    //
+    // Returns the server's URL. Connections are closed after one response.
+    fn serve(ip: &str, respond: impl Fn(&str) -> String + Send + 'static) -> String {
+        let listener = std::net::TcpListener::bind((ip, 0)).unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buffer = [0; 4096];
+                let read = stream.read(&mut buffer).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]).to_lowercase();
+                let _ = stream.write_all(respond(&request).as_bytes());
+            }
+        });
+        url
+    }
 
     #[tokio::test]
     async fn test_check_valid_link() {
@@ -281,14 +688,157 @@ mod tests {
             url: "https://example.com".to_string(),
             status: LinkStatus::Ok,
             message: None,
+            locations: Vec::new(),
         };
         assert!(ok_result.is_ok());
 
         let broken_result = LinkCheckResult {
             url: "https://example.com".to_string(),
-            status: LinkStatus::Broken,
+            status: LinkStatus::Broken { code: 404 },
             message: None,
+            locations: Vec::new(),
         };
         assert!(!broken_result.is_ok());
     }
+
+    #[test]
+    fn test_status_name_matches_json() {
+        let result = LinkCheckResult {
+            url: "https://example.com".to_string(),
+            status: LinkStatus::TooManyRedirects,
+            message: None,
+            locations: Vec::new(),
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["status"], result.status.name());
+    }
+
+    #[test]
+    fn test_status_from_code() {
+        assert_eq!(LinkStatus::from_code(410), LinkStatus::Broken { code: 410 });
+        assert_eq!(LinkStatus::from_code(401), LinkStatus::Unauthorized { code: 401 });
+        assert_eq!(LinkStatus::from_code(403), LinkStatus::Forbidden { code: 403 });
+        assert_eq!(LinkStatus::from_code(429), LinkStatus::RateLimited { code: 429 });
+        assert_eq!(LinkStatus::from_code(418), LinkStatus::ClientError { code: 418 });
+        assert_eq!(LinkStatus::from_code(503), LinkStatus::ServerError { code: 503 });
+        assert_eq!(LinkStatus::from_code(999), LinkStatus::UnexpectedStatus { code: 999 });
+        assert_eq!(LinkStatus::from_code(503).code(), Some(503));
+        assert_eq!(LinkStatus::Timeout.code(), None);
+    }
+
+    #[test]
+    fn test_status_json_round_trip() {
+        let result = LinkCheckResult {
+            url: "https://example.com".to_string(),
+            status: LinkStatus::Redirect {
+                code: 301,
+                target: "https://example.com/new".to_string(),
+            },
+            message: None,
+            locations: Vec::new(),
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["status"], "redirect");
+        assert_eq!(json["code"], 301);
+        assert_eq!(json["target"], "https://example.com/new");
+
+        let parsed: LinkCheckResult = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.status, result.status);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_url_and_refused_connection() {
+        // Bind a free port, then close it again: nothing listens there
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let refused = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+
+        let mut results = check_links(vec!["not a url".to_string(), refused.clone()], 2).await;
+        results.sort_by(|a, b| a.url.cmp(&b.url));
+
+        assert_eq!(results[0].url, refused);
+        assert_eq!(results[0].status, LinkStatus::ConnectionRefused);
+        assert_eq!(results[1].status, LinkStatus::InvalidUrl);
+    }
+
+    #[tokio::test]
+    async fn test_not_modified_without_cache_entry() {
+        let path = std::env::temp_dir().join(format!("link-guardian-test-{}-304.json", std::process::id()));
+        let cache = ResultCache::load(path, CacheTtls::default()).unwrap().shared();
+
+        // The entry disappears while the conditional request is in flight
+        let server_cache = cache.clone();
+        let url = serve("127.0.0.1", move |request| {
+            if request.contains("if-none-match") {
+                server_cache.lock().unwrap().clear();
+                "HTTP/1.1 304 Not Modified\r\nConnection: close\r\n\r\n".to_string()
+            } else {
+                "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+            }
+        });
+
+        let cached = LinkCheckResult {
+            url: url.clone(),
+            status: LinkStatus::Ok,
+            message: Some("HTTP 200".to_string()),
+            locations: Vec::new(),
+        };
+        let etag = Validators {
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+        };
+        cache.lock().unwrap().insert(&cached, Some(200), etag);
+
+        let options = CheckOptions {
+            cache: Some(cache),
+            ..CheckOptions::default()
+        };
+        let client = Client::new();
+        let result = check_single_link(client, url, &options).await;
+        assert_eq!(result.status, LinkStatus::Ok);
+        assert_eq!(result.message.as_deref(), Some("HTTP 200"));
+    }
+
+    #[tokio::test]
+    async fn test_redirect_keeps_per_domain_headers() {
+        // The second host records what it receives; /gone is a dead link
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let log = received.clone();
+        let other = serve("127.0.0.2", move |request| {
+            log.lock().unwrap().push(request.to_string());
+            match request.starts_with("head /gone ") {
+                true => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                false => "HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+            }
+        });
+        let location = other.clone();
+        let url = serve("127.0.0.1", move |request| {
+            let path = request.split(' ').nth(1).unwrap_or("/").trim_start_matches('/');
+            format!("HTTP/1.1 302 Found\r\nLocation: {}{}\r\nConnection: close\r\n\r\n", location, path)
+        });
+
+        // Without per-domain headers the redirect is followed
+        let results = check_links_with(vec![url.clone()], 1, &CheckOptions::default(), |_| {}).await;
+        assert_eq!(results[0].status, LinkStatus::Ok);
+        assert!(!received.lock().unwrap().is_empty());
+        received.lock().unwrap().clear();
+
+        // A key for 127.0.0.1 must not reach 127.0.0.2, but the target is
+        // still checked, so a dead one is still reported
+        let options = CheckOptions {
+            headers: RequestHeaders {
+                headers_for: vec!["127.0.0.1=X-Api-Key: secret".parse().unwrap()],
+                ..RequestHeaders::default()
+            },
+            ..CheckOptions::default()
+        };
+        let gone = format!("{}gone", url);
+        let mut results = check_links_with(vec![url, gone], 1, &options, |_| {}).await;
+        results.sort_by(|a, b| a.url.cmp(&b.url));
+        assert_eq!(results[0].status, LinkStatus::Ok);
+        assert_eq!(results[1].status, LinkStatus::Broken { code: 404 });
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert!(received.iter().all(|request| !request.contains("x-api-key")));
+    }
 }
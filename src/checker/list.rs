@@ -0,0 +1,90 @@
+// src/checker/list.rs
+// =============================================================================
+// This module reads a plain list of URLs, one per line.
+//
+// Sometimes the links don't live in a README or on a website but come from a
+// database export or a `grep`. The `urls` subcommand checks such a list:
+//
+//   # Links from the CMS export
+//   https://example.com/pricing
+//   https://example.com/blog/2023/launch
+//
+// Blank lines and lines starting with # are skipped. Surrounding whitespace
+// is trimmed. Every other line is taken as a URL as-is; lines that aren't
+// valid URLs are still checked and end up with the invalid_url status, so
+// mistakes in the list show up in the report instead of disappearing.
+//
+// Rust concepts:
+// - lines() + enumerate(): Walking through text with line numbers
+// - filter_map(): Skipping some items while transforming the others
+// =============================================================================
+
+use super::location::TextPosition;
+
+// Extracts the URLs from a newline-separated list
+//
+// Parameters:
+//   text: the whole list (a file or stdin)
+//
+// Returns: each URL with its line and column (1-based)
+//
+// Example:
+//   text = "# comment\n\n  https://a.com\n"
+//   result = [("https://a.com", line 3, column 3)]
+pub fn extract_list_links(text: &str) -> Vec<(String, TextPosition)> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let url = line.trim();
+            if url.is_empty() || url.starts_with('#') {
+                return None;
+            }
+
+            // Column of the first non-space character (in characters, not bytes)
+            let indent = line.len() - line.trim_start().len();
+            let position = TextPosition {
+                line: index + 1,
+                column: line[..indent].chars().count() + 1,
+            };
+            Some((url.to_string(), position))
+        })
+        .collect()
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why does enumerate() start at 0 but lines start at 1?
+//    - enumerate() counts like an index (0, 1, 2, ...)
+//    - Editors number lines from 1, so we add 1 for the report
+//
+// 2. What does filter_map() do?
+//    - The closure returns an Option for each item
+//    - None drops the item, Some(x) keeps x
+//    - It's filter() and map() in one step
+//
+// 3. Why trim() before checking for '#'?
+//    - An indented comment like "   # old links" is still a comment
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_list_links() {
+        let text = "# exported 2024-05-01\n\nhttps://a.com\n   https://b.com/page  \n  # https://skipped.com\nnot a url\n";
+        let links = extract_list_links(text);
+
+        let urls: Vec<&str> = links.iter().map(|(url, _)| url.as_str()).collect();
+        assert_eq!(urls, ["https://a.com", "https://b.com/page", "not a url"]);
+        assert_eq!(links[1].1, TextPosition { line: 4, column: 4 });
+    }
+
+    #[test]
+    fn test_windows_line_endings() {
+        let links = extract_list_links("https://a.com\r\nhttps://b.com\r\n");
+        assert_eq!(links[0].0, "https://a.com");
+        assert_eq!(links[1].1.line, 2);
+    }
+}
@@ -0,0 +1,202 @@
+// src/checker/location.rs
+// =============================================================================
+// This module tracks WHERE each link was found.
+//
+// A bare list of URLs tells you what is broken, but not where to fix it.
+// Every extractor records a SourceLocation next to each URL (file + line, or
+// the page that linked to it), and LinkCollection groups them by URL so each
+// unique link is only checked once while keeping every place it appears.
+//
+// Rust concepts:
+// - HashMap: To group locations by URL
+// - impl Display: To control how a type is printed with {}
+// - serde attributes: To keep the JSON output compact
+// =============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use super::LinkCheckResult;
+
+// A position inside a piece of text (both values start at 1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl TextPosition {
+    // Converts a byte offset into a line/column position
+    //
+    // Example: offset 7 in "ab\ncdefg" is line 2, column 5
+    pub fn from_offset(text: &str, offset: usize) -> Self {
+        let before = &text[..offset.min(text.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+        TextPosition { line, column }
+    }
+}
+
+// Describes where a link was found
+//
+// Examples:
+//   README.md line 12             -> source = "README.md", line = Some(12)
+//   notebooks/intro.ipynb cell 3  -> source = "notebooks/intro.ipynb", cell = Some(3)
+//   a crawled page                -> source = "https://example.com/about"
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SourceLocation {
+    /// File path or page URL containing the link
+    pub source: String,
+    /// Notebook cell number (starts at 1), only set for Jupyter notebooks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cell: Option<usize>,
+    /// Line number (starts at 1); inside a notebook it is the line in the cell
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Column number (starts at 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl SourceLocation {
+    // A location we only know the file or page of (no line information)
+    pub fn new(source: &str) -> Self {
+        SourceLocation {
+            source: source.to_string(),
+            cell: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    // A location at a specific line/column of a file
+    pub fn at(source: &str, position: TextPosition) -> Self {
+        SourceLocation {
+            line: Some(position.line),
+            column: Some(position.column),
+            ..SourceLocation::new(source)
+        }
+    }
+}
+
+// Prints locations the way editors and compilers do: "README.md:12:5"
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)?;
+        if let Some(cell) = self.cell {
+            write!(f, " [cell {}]", cell)?;
+        }
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Collects links from many sources, de-duplicating by URL
+//
+// Links are kept in the order they were first seen so output is stable.
+#[derive(Debug, Default)]
+pub struct LinkCollection {
+    urls: Vec<String>,
+    locations: HashMap<String, Vec<SourceLocation>>,
+}
+
+impl LinkCollection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Records that `url` was found at `location`
+    pub fn add(&mut self, url: String, location: SourceLocation) {
+        match self.locations.get_mut(&url) {
+            Some(existing) => existing.push(location),
+            None => {
+                self.urls.push(url.clone());
+                self.locations.insert(url, vec![location]);
+            }
+        }
+    }
+
+    // Number of unique URLs
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+
+    // The unique URLs, ready to pass to check_links()
+    pub fn urls(&self) -> Vec<String> {
+        self.urls.clone()
+    }
+
+    // Copies the recorded locations into the matching check results
+    pub fn attach_locations(&self, results: &mut [LinkCheckResult]) {
+        for result in results {
+            self.attach_locations_to(result);
+        }
+    }
+
+    // Copies the recorded locations into a single check result
+    pub fn attach_locations_to(&self, result: &mut LinkCheckResult) {
+        if let Some(locations) = self.locations.get(&result.url) {
+            result.locations = locations.clone();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why ..SourceLocation::new(source)?
+//    - This is "struct update syntax"
+//    - It fills every field we didn't list from another value
+//    - Handy when most fields keep their default
+//
+// 2. What does impl fmt::Display do?
+//    - It teaches Rust how to print our type with {} in format!/println!
+//    - We also get .to_string() for free
+//
+// 3. Why skip_serializing_if?
+//    - Fields that are None are left out of the JSON entirely
+//    - Keeps the output short for sources without line numbers
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_from_offset() {
+        let text = "first\nsecond line\nthird";
+        assert_eq!(TextPosition::from_offset(text, 0), TextPosition { line: 1, column: 1 });
+        assert_eq!(TextPosition::from_offset(text, 13), TextPosition { line: 2, column: 8 });
+    }
+
+    #[test]
+    fn test_display_location() {
+        let mut location = SourceLocation::at("README.md", TextPosition { line: 12, column: 5 });
+        assert_eq!(location.to_string(), "README.md:12:5");
+
+        location.cell = Some(3);
+        assert_eq!(location.to_string(), "README.md [cell 3]:12:5");
+    }
+
+    #[test]
+    fn test_collection_deduplicates() {
+        let mut links = LinkCollection::new();
+        links.add("https://a.com".to_string(), SourceLocation::new("one.md"));
+        links.add("https://b.com".to_string(), SourceLocation::new("one.md"));
+        links.add("https://a.com".to_string(), SourceLocation::new("two.md"));
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(links.urls(), vec!["https://a.com", "https://b.com"]);
+    }
+}
@@ -15,6 +15,8 @@
 
 use pulldown_cmark::{Parser, Event, Tag};
 
+use super::location::TextPosition;
+
 // Extracts all HTTP/HTTPS links from Markdown text
 //
 // Parameters:
@@ -27,22 +29,44 @@ use pulldown_cmark::{Parser, Event, Tag};
 //
 // Example output:
 //   vec!["https://www.rust-lang.org"]
+//
+// The scanners use extract_markdown_links_with_positions so they can report
+// line numbers; this simpler version is kept for callers that only need URLs.
+#[allow(dead_code)]
 pub fn extract_markdown_links(markdown: &str) -> Vec<String> {
+    extract_markdown_links_with_positions(markdown)
+        .into_iter()
+        .map(|(url, _position)| url)
+        .collect()
+}
+
+// Same as extract_markdown_links, but also returns where each link starts
+//
+// The position points at the opening '[' of the link so editors and CI
+// annotations can jump straight to it.
+//
+// Example input:
+//   "Intro\n\nSee [Rust](https://www.rust-lang.org)"
+//
+// Example output:
+//   vec![("https://www.rust-lang.org", TextPosition { line: 3, column: 5 })]
+pub fn extract_markdown_links_with_positions(markdown: &str) -> Vec<(String, TextPosition)> {
     let mut links = Vec::new();
 
     // Create a Markdown parser
-    // This produces an iterator of events as it parses the text
-    let parser = Parser::new(markdown);
+    // into_offset_iter() gives us each event together with the byte range
+    // of the source text it came from
+    let parser = Parser::new(markdown).into_offset_iter();
 
     // Track if we're currently inside a link
     // We need this because markdown parsing produces multiple events per link:
     // 1. Start(Link) - link begins
     // 2. Text - the link text
     // 3. End(Link) - link ends
-    let mut current_link: Option<String> = None;
+    let mut current_link: Option<(String, TextPosition)> = None;
 
     // Iterate through all markdown events
-    for event in parser {
+    for (event, range) in parser {
         match event {
             // When we encounter the start of a link tag
             // In pulldown-cmark 0.9, Link is Tag::Link(link_type, dest_url, title)
@@ -54,15 +78,16 @@ pub fn extract_markdown_links(markdown: &str) -> Vec<String> {
                 // Only keep HTTP/HTTPS links
                 // Skip mailto:, tel:, javascript:, etc.
                 if is_http_link(&url) {
-                    current_link = Some(url);
+                    let position = TextPosition::from_offset(markdown, range.start);
+                    current_link = Some((url, position));
                 }
             }
 
             // When we encounter the end of a link tag
             Event::End(Tag::Link(..)) => {
                 // If we were tracking a link, add it to our results
-                if let Some(url) = current_link.take() {
-                    links.push(url);
+                if let Some(link) = current_link.take() {
+                    links.push(link);
                 }
             }
 
@@ -160,4 +185,17 @@ mod tests {
         let links = extract_markdown_links(markdown);
         assert_eq!(links.len(), 0);
     }
+
+    #[test]
+    fn test_link_positions() {
+        let markdown = "# Title\n\nRead [the book](https://doc.rust-lang.org/book/) first.";
+        let links = extract_markdown_links_with_positions(markdown);
+        assert_eq!(
+            links,
+            vec![(
+                "https://doc.rust-lang.org/book/".to_string(),
+                TextPosition { line: 3, column: 6 }
+            )]
+        );
+    }
 }
@@ -4,8 +4,18 @@
 //
 // Submodules:
 // - http: Makes HTTP requests to check if links are alive
+// - explain: Step-by-step trace of how one link is checked (`explain`)
 // - markdown: Extracts links from markdown text
 // - html: Extracts links from HTML pages
+// - css: Extracts url() and @import links from stylesheets
+// - notebook: Extracts links from Jupyter notebooks
+// - comments: Extracts links from comments in source code
+// - list: Reads a plain list of URLs, one per line
+// - location: Tracks where each link was found (file, line, page)
+// - policy: Which status codes are accepted and which statuses fail the run
+// - headers: Extra headers, cookies and credentials for requests
+// - client: User-Agent, timeouts and redirect limit of the HTTP clients
+// - network: Proxy and extra trusted CA certificates for every client
 //
 // This file (mod.rs) is the module root - it ties everything together and
 // exports the public API that other parts of our application can use.
@@ -18,15 +28,41 @@
 
 // Declare submodules (tells Rust to include these files)
 mod http;
+mod explain;
 mod markdown;
 mod html;
+mod css;
+mod notebook;
+mod comments;
+mod list;
+mod location;
+mod policy;
+mod headers;
+mod client;
+mod network;
 
 // Re-export public items from submodules
 // This lets users write `checker::check_links()` instead of
 // `checker::http::check_links()`
-pub use http::{check_links, LinkCheckResult, LinkStatus};
+pub use http::{check_links_with, send_following, CheckOptions, LinkCheckResult, LinkStatus};
+// Without a callback or options, for callers that just want the results
+#[allow(unused_imports)]
+pub use http::check_links;
+pub use explain::explain;
+pub use markdown::extract_markdown_links_with_positions;
+// Only the URLs, for callers that don't need line numbers (the scanners do)
+#[allow(unused_imports)]
 pub use markdown::extract_markdown_links;
-pub use html::extract_html_links;
+pub use html::{extract_html_links, extract_stylesheet_links};
+pub use css::{extract_css_imports, extract_css_links};
+pub use notebook::extract_notebook_links;
+pub use comments::{extract_comment_links, is_source_file};
+pub use list::extract_list_links;
+pub use location::{LinkCollection, SourceLocation};
+pub use policy::{AcceptPolicy, FailurePolicy, ForDomain, Outcome, StatusCodes};
+pub use headers::{Cookie, Credentials, Header, RequestHeaders, Secret};
+pub use client::{ClientSettings, Clients, Timeout, UserAgent, DEFAULT_USER_AGENT};
+pub use network::{CaBundle, Network, ProxyUrl};
 
 // -----------------------------------------------------------------------------
 // BEGINNER NOTES:
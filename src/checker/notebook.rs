@@ -0,0 +1,185 @@
+// src/checker/notebook.rs
+// =============================================================================
+// This module extracts links from Jupyter notebooks (.ipynb files).
+//
+// A notebook is a JSON document containing a list of cells:
+// - Markdown cells hold prose (and most of the links)
+// - Code cells can have outputs, some of which are rendered HTML
+//
+// We don't parse links ourselves here - we reuse the existing extractors:
+// - Markdown cells go through the markdown extractor
+// - HTML outputs go through extract_html_links
+//
+// Each link is reported as "notebook path + cell number + line" so users can
+// find it in Jupyter without searching through raw JSON.
+//
+// Rust concepts:
+// - serde_json::Value: Working with JSON without defining structs first
+// - Option chaining: .get()/.and_then() to walk nested JSON safely
+// =============================================================================
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+use super::html::extract_html_links;
+use super::location::{SourceLocation, TextPosition};
+use super::markdown::extract_markdown_links_with_positions;
+
+// Extracts all HTTP/HTTPS links from a Jupyter notebook
+//
+// Parameters:
+//   notebook: the raw .ipynb file contents (JSON)
+//   path: the notebook's path, used in the reported locations
+//   base_url: URL the notebook is served from (for relative links in HTML)
+//
+// Returns: Vec of (url, location) pairs, or an error if the JSON is invalid
+//
+// Cell numbers start at 1, counting every cell in the notebook
+// (markdown, code and raw), which matches the order shown in Jupyter.
+pub fn extract_notebook_links(
+    notebook: &str,
+    path: &str,
+    base_url: &str,
+) -> Result<Vec<(String, SourceLocation)>> {
+    let json: Value = serde_json::from_str(notebook)
+        .map_err(|e| anyhow!("Invalid notebook JSON in {}: {}", path, e))?;
+
+    let cells = json
+        .get("cells")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("Notebook {} has no cells (only nbformat 4 is supported)", path))?;
+
+    let mut links = Vec::new();
+
+    for (index, cell) in cells.iter().enumerate() {
+        let cell_number = index + 1;
+        let cell_type = cell.get("cell_type").and_then(Value::as_str).unwrap_or("");
+
+        // Markdown cells: run the source through the markdown extractor
+        if cell_type == "markdown" {
+            let source = join_source(cell.get("source"));
+            for (url, position) in extract_markdown_links_with_positions(&source) {
+                links.push((url, cell_location(path, cell_number, Some(position))));
+            }
+        }
+
+        // Code cells: look for rendered HTML in the outputs
+        let outputs = cell.get("outputs").and_then(Value::as_array);
+        for output in outputs.into_iter().flatten() {
+            let data = match output.get("data") {
+                Some(data) => data,
+                None => continue,
+            };
+
+            if let Some(html) = data.get("text/html") {
+                let html = join_source(Some(html));
+                for url in extract_html_links(&html, base_url) {
+                    let position = find_position(&html, &url);
+                    links.push((url, cell_location(path, cell_number, position)));
+                }
+            }
+
+            if let Some(markdown) = data.get("text/markdown") {
+                let markdown = join_source(Some(markdown));
+                for (url, position) in extract_markdown_links_with_positions(&markdown) {
+                    links.push((url, cell_location(path, cell_number, Some(position))));
+                }
+            }
+        }
+    }
+
+    Ok(links)
+}
+
+// Notebook text fields are either one string or a list of lines
+//
+// Both of these mean the same thing:
+//   "source": "line one\nline two"
+//   "source": ["line one\n", "line two"]
+fn join_source(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(lines)) => lines.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+// Builds the location for a link inside a notebook cell
+fn cell_location(path: &str, cell: usize, position: Option<TextPosition>) -> SourceLocation {
+    let mut location = match position {
+        Some(position) => SourceLocation::at(path, position),
+        None => SourceLocation::new(path),
+    };
+    location.cell = Some(cell);
+    location
+}
+
+// Finds where a URL appears in a piece of text
+//
+// extract_html_links returns normalized URLs, which may have gained a
+// trailing slash ("https://a.com" -> "https://a.com/"), so we also try
+// without it. Relative links won't be found literally; we return None then.
+fn find_position(text: &str, url: &str) -> Option<TextPosition> {
+    text.find(url)
+        .or_else(|| text.find(url.trim_end_matches('/')))
+        .map(|offset| TextPosition::from_offset(text, offset))
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why serde_json::Value instead of structs?
+//    - Notebooks have many optional fields we don't care about
+//    - Value lets us pick out just "cells", "source" and "outputs"
+//    - .get("key") returns Option<&Value>, so missing fields are not errors
+//
+// 2. What does outputs.into_iter().flatten() do?
+//    - outputs is Option<&Vec<Value>>
+//    - Iterating an Option gives zero or one items; flatten() then walks
+//      the Vec inside, so cells without outputs are simply skipped
+//
+// 3. Why collect() into a String?
+//    - An iterator of &str can be collected straight into a String
+//    - It concatenates all the pieces in order
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOTEBOOK: &str = r##"{
+        "cells": [
+            {"cell_type": "code", "source": ["print('hi')"], "outputs": []},
+            {"cell_type": "markdown", "source": ["# Intro\n", "\n", "See [Rust](https://www.rust-lang.org)\n"]},
+            {"cell_type": "code", "source": "display(x)", "outputs": [
+                {"output_type": "display_data", "data": {
+                    "text/html": ["<p>\n", "<a href=\"https://docs.rs\">docs</a></p>"]
+                }}
+            ]}
+        ],
+        "nbformat": 4
+    }"##;
+
+    #[test]
+    fn test_markdown_cell_links() {
+        let links = extract_notebook_links(NOTEBOOK, "intro.ipynb", "https://example.com/").unwrap();
+        let (url, location) = &links[0];
+        assert_eq!(url, "https://www.rust-lang.org");
+        assert_eq!(location.cell, Some(2));
+        assert_eq!(location.line, Some(3));
+    }
+
+    #[test]
+    fn test_html_output_links() {
+        let links = extract_notebook_links(NOTEBOOK, "intro.ipynb", "https://example.com/").unwrap();
+        let (url, location) = &links[1];
+        assert_eq!(url, "https://docs.rs/");
+        assert_eq!(location.cell, Some(3));
+        assert_eq!(location.line, Some(2));
+    }
+
+    #[test]
+    fn test_invalid_notebook() {
+        assert!(extract_notebook_links("not json", "bad.ipynb", "https://example.com/").is_err());
+    }
+}
@@ -0,0 +1,305 @@
+// src/checker/policy.rs
+// =============================================================================
+// This module decides what counts as a good link and what fails the run.
+//
+// Two separate questions:
+//
+// 1. Which HTTP status codes are acceptable? (AcceptPolicy)
+//    By default only 2xx. Sites behind a login answer 403, rate limiters
+//    answer 429 - users can accept those, for every site or per domain:
+//      --accept 200-299,429
+//      --accept-for intranet.example.com=200-299,401,403
+//    An accepted response is reported as "ok".
+//
+// 2. Which statuses make the exit code 1? (FailurePolicy)
+//    By default every status except ok and redirect. This can be narrowed:
+//      --fail-on broken,dns_error     only these fail
+//      --warn-on redirect,timeout     these are reported, but never fail
+//    With --baseline, failures already recorded there are warnings too.
+//
+// Rust concepts:
+// - FromStr: Parsing our own types from strings (clap uses it for flags)
+// - RangeInclusive: 200..=299 style ranges
+// =============================================================================
+
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use url::Url;
+
+use super::{LinkCheckResult, LinkStatus};
+use crate::cache::cache_key;
+
+// A set of HTTP status codes, e.g. "200-299,403"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusCodes(Vec<RangeInclusive<u16>>);
+
+impl StatusCodes {
+    // Returns true if `code` is in one of the ranges
+    pub fn contains(&self, code: u16) -> bool {
+        self.0.iter().any(|range| range.contains(&code))
+    }
+}
+
+// Only 2xx responses are accepted unless configured otherwise
+impl Default for StatusCodes {
+    fn default() -> Self {
+        StatusCodes(vec![200..=299])
+    }
+}
+
+// Parses "200-299,403,429"
+impl FromStr for StatusCodes {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut ranges = Vec::new();
+        for part in text.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (start, end) = part.split_once('-').unwrap_or((part, part));
+            let start = parse_code(start)?;
+            let end = parse_code(end)?;
+            if start > end {
+                return Err(format!("invalid range '{}': start is after end", part));
+            }
+            ranges.push(start..=end);
+        }
+        if ranges.is_empty() {
+            return Err("expected status codes like 200-299,403".to_string());
+        }
+        Ok(StatusCodes(ranges))
+    }
+}
+
+fn parse_code(text: &str) -> Result<u16, String> {
+    match text.trim().parse::<u16>() {
+        Ok(code) if (100..=999).contains(&code) => Ok(code),
+        _ => Err(format!("'{}' is not an HTTP status code", text.trim())),
+    }
+}
+
+// A setting that only applies to one domain (and its subdomains)
+//
+// Parsed from "DOMAIN=VALUE", where VALUE is parsed as T. Used for every
+// per-domain flag, e.g. --accept-for example.com=200-299,403
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForDomain<T> {
+    pub domain: String,
+    pub value: T,
+}
+
+impl<T> FromStr for ForDomain<T>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (domain, value) = text
+            .split_once('=')
+            .ok_or_else(|| format!("expected DOMAIN=VALUE, got '{}'", text))?;
+        Ok(ForDomain {
+            domain: domain.trim().trim_start_matches("*.").to_lowercase(),
+            value: value.trim().parse().map_err(|e: T::Err| e.to_string())?,
+        })
+    }
+}
+
+impl<T> ForDomain<T> {
+    // Returns true if this entry's domain matches the host of `url`
+    pub fn applies_to(&self, url: &str) -> bool {
+        Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_lowercase))
+            .is_some_and(|host| domain_matches(&host, &self.domain))
+    }
+
+    // Every entry whose domain matches the host of `url`
+    pub fn matching<'a>(entries: &'a [ForDomain<T>], url: &str) -> impl Iterator<Item = &'a ForDomain<T>> {
+        let host = Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_lowercase));
+        entries
+            .iter()
+            .filter(move |entry| host.as_deref().is_some_and(|host| domain_matches(host, &entry.domain)))
+    }
+
+    // The value of the most specific entry matching `url`, if any
+    pub fn most_specific<'a>(entries: &'a [ForDomain<T>], url: &str) -> Option<&'a T> {
+        Self::matching(entries, url)
+            .max_by_key(|entry| entry.domain.len())
+            .map(|entry| &entry.value)
+    }
+}
+
+// Which status codes count as "ok"
+#[derive(Debug, Clone, Default)]
+pub struct AcceptPolicy {
+    /// Codes accepted for every domain without its own entry
+    pub default: StatusCodes,
+    /// Per-domain overrides; the most specific matching domain wins
+    pub per_domain: Vec<ForDomain<StatusCodes>>,
+}
+
+impl AcceptPolicy {
+    // Returns true if `code` is acceptable for `url`
+    pub fn accepts(&self, url: &str, code: u16) -> bool {
+        ForDomain::most_specific(&self.per_domain, url)
+            .unwrap_or(&self.default)
+            .contains(code)
+    }
+}
+
+// Returns true if `host` is `domain` or one of its subdomains
+//
+// Example: "docs.example.com" matches "example.com", "badexample.com" doesn't
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+// What a result means for the exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Nothing to report
+    Pass,
+    /// Reported, but doesn't fail the run
+    Warn,
+    /// Fails the run (exit code 1)
+    Fail,
+}
+
+// Which statuses fail the run and which are only warnings
+//
+// Statuses are named like in the JSON output ("broken", "dns_error", ...).
+#[derive(Debug, Clone, Default)]
+pub struct FailurePolicy {
+    /// Only these statuses fail; None = every status except ok and redirect
+    pub fail_on: Option<Vec<String>>,
+    /// These statuses are warnings (unless also listed in fail_on)
+    pub warn_on: Vec<String>,
+    /// Failures already in the --baseline, by cache_key(); only warnings
+    pub known_failures: HashSet<String>,
+}
+
+impl FailurePolicy {
+    // Decides what a status means for the exit code
+    pub fn outcome(&self, status: &LinkStatus) -> Outcome {
+        let name = status.name();
+        let listed = |names: &[String]| names.iter().any(|n| n == name);
+
+        // --fail-on beats --warn-on; without --fail-on everything but
+        // ok and redirect fails
+        match &self.fail_on {
+            Some(names) if listed(names) => Outcome::Fail,
+            _ if listed(&self.warn_on) => Outcome::Warn,
+            None if !matches!(status, LinkStatus::Ok | LinkStatus::Redirect { .. }) => Outcome::Fail,
+            _ => Outcome::Pass,
+        }
+    }
+
+    // Decides what a result means, with known failures turned into warnings
+    //
+    // This is what reports and the exit code go by.
+    pub fn outcome_of(&self, result: &LinkCheckResult) -> Outcome {
+        match self.outcome(&result.status) {
+            Outcome::Fail if self.known_failures.contains(&cache_key(&result.url)) => Outcome::Warn,
+            outcome => outcome,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. What does implementing FromStr give us?
+//    - "200-299,403".parse::<StatusCodes>() works like parsing a number
+//    - clap uses FromStr automatically, so a bad --accept value produces a
+//      clear error before anything runs
+//
+// 2. What is 200..=299?
+//    - An inclusive range: 200 up to AND including 299
+//    - range.contains(&code) checks whether a number is inside it
+//
+// 3. Why does the longest matching domain win?
+//    - With entries for example.com and api.example.com, a link to
+//      api.example.com should use the more specific rule
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_codes() {
+        let codes: StatusCodes = "200-299, 403".parse().unwrap();
+        assert!(codes.contains(204));
+        assert!(codes.contains(403));
+        assert!(!codes.contains(404));
+
+        assert!("299-200".parse::<StatusCodes>().is_err());
+        assert!("abc".parse::<StatusCodes>().is_err());
+        assert!("".parse::<StatusCodes>().is_err());
+    }
+
+    #[test]
+    fn test_accept_per_domain() {
+        let policy = AcceptPolicy {
+            default: StatusCodes::default(),
+            per_domain: vec![
+                "example.com=200-299,403".parse().unwrap(),
+                "api.example.com=429".parse().unwrap(),
+            ],
+        };
+        assert!(policy.accepts("https://docs.example.com/x", 403));
+        assert!(!policy.accepts("https://other.org/x", 403));
+        assert!(policy.accepts("https://api.example.com/x", 429));
+        assert!(!policy.accepts("https://api.example.com/x", 403));
+        assert!(!policy.accepts("https://badexample.com/x", 403));
+    }
+
+    #[test]
+    fn test_for_domain() {
+        let entries: Vec<ForDomain<StatusCodes>> =
+            vec!["*.Example.com=401".parse().unwrap(), "api.example.com = 429".parse().unwrap()];
+        assert_eq!(entries[0].domain, "example.com");
+        let codes_for = |url| ForDomain::most_specific(&entries, url).map(|codes| codes.contains(429));
+        assert_eq!(codes_for("https://api.example.com/x"), Some(true));
+        assert_eq!(codes_for("https://docs.example.com/x"), Some(false));
+        assert_eq!(codes_for("not a url"), None);
+        assert_eq!(ForDomain::matching(&entries, "https://api.example.com/x").count(), 2);
+        assert!("example.com".parse::<ForDomain<StatusCodes>>().is_err());
+    }
+
+    #[test]
+    fn test_failure_policy() {
+        let redirect = LinkStatus::Redirect {
+            code: 301,
+            target: "x".to_string(),
+        };
+        let default = FailurePolicy::default();
+        assert_eq!(default.outcome(&LinkStatus::Broken { code: 404 }), Outcome::Fail);
+        assert_eq!(default.outcome(&redirect), Outcome::Pass);
+
+        let policy = FailurePolicy {
+            fail_on: Some(vec!["broken".to_string(), "dns_error".to_string()]),
+            warn_on: vec!["redirect".to_string(), "timeout".to_string()],
+            ..FailurePolicy::default()
+        };
+        assert_eq!(policy.outcome(&LinkStatus::Broken { code: 404 }), Outcome::Fail);
+        assert_eq!(policy.outcome(&LinkStatus::Timeout), Outcome::Warn);
+        assert_eq!(policy.outcome(&redirect), Outcome::Warn);
+        assert_eq!(policy.outcome(&LinkStatus::SslError), Outcome::Pass);
+        assert_eq!(default.outcome(&LinkStatus::Forbidden { code: 403 }), Outcome::Fail);
+
+        let known = FailurePolicy {
+            known_failures: HashSet::from(["https://example.com/gone".to_string()]),
+            ..FailurePolicy::default()
+        };
+        let result = |url: &str| LinkCheckResult {
+            url: url.to_string(),
+            status: LinkStatus::Broken { code: 404 },
+            message: None,
+            locations: Vec::new(),
+        };
+        assert_eq!(known.outcome_of(&result("https://Example.com/gone#top")), Outcome::Warn);
+        assert_eq!(known.outcome_of(&result("https://example.com/new")), Outcome::Fail);
+    }
+}
@@ -12,7 +12,17 @@
 // - Derive macros: Automatically generate code for our types
 // =============================================================================
 
-use clap::{Parser, Subcommand};
+use anyhow::Result;
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::cache::{CacheTtls, ResultCache, StatusTtl};
+use crate::checker::{
+    AcceptPolicy, CaBundle, CheckOptions, ClientSettings, Cookie, Credentials, FailurePolicy, ForDomain, Header,
+    LinkStatus, Network, ProxyUrl, RequestHeaders, Secret, StatusCodes, Timeout, UserAgent, DEFAULT_USER_AGENT,
+};
 
 // This struct represents our entire CLI application
 //
@@ -31,9 +41,25 @@ pub struct Cli {
     // will hold one of the subcommands defined in the Commands enum
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Only print the report, warnings and errors (no progress messages)
+    ///
+    /// Progress always goes to stderr, so this only affects the terminal
+    #[arg(long, short = 'q', global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Print extra detail about each step (-v), or even more (-vv)
+    ///
+    /// RUST_LOG (e.g. RUST_LOG=link_guardian=debug) overrides this
+    #[arg(long, short = 'v', global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Also write detailed logs as JSON lines to this file
+    #[arg(long, global = true, value_name = "FILE")]
+    pub log_file: Option<PathBuf>,
 }
 
-// This enum defines our subcommands (github, site)
+// This enum defines our subcommands (github, site, urls, explain, report, diff, cache)
 //
 // Each variant represents a different subcommand the user can run
 // The fields inside each variant become the arguments for that subcommand
@@ -48,12 +74,16 @@ pub enum Commands {
         /// This is a positional argument (required, no flag needed)
         repo_url: String,
 
-        /// Output results in JSON format instead of a table
+        /// Output options (--format, --json)
         ///
-        /// This is an optional flag: --json
-        /// #[arg(long)] creates a flag from the field name
-        #[arg(long)]
-        json: bool,
+        /// #[command(flatten)] pulls in all the flags defined on OutputArgs,
+        /// so every subcommand shares them without repeating the definitions
+        #[command(flatten)]
+        output: OutputArgs,
+
+        /// Link checking options (--accept, --fail-on, ...)
+        #[command(flatten)]
+        check: CheckArgs,
 
         /// Number of concurrent link checks (default: 500)
         ///
@@ -61,6 +91,13 @@ pub enum Commands {
         /// Example: --concurrency 1000
         #[arg(long, short = 'c', default_value_t = 500)]
         concurrency: usize,
+
+        /// Also check links found in source code comments and docstrings
+        ///
+        /// Looks at //, ///, //!, /* */ and # comments plus Python docstrings
+        /// in common languages (Rust, C, Go, Java, JS/TS, Python, Shell, ...)
+        #[arg(long)]
+        check_comments: bool,
     },
 
     /// Scan a website for broken links
@@ -72,11 +109,16 @@ pub enum Commands {
         /// This is a positional argument (required)
         website_url: String,
 
-        /// Output results in JSON format instead of a table
+        /// Output options (--format, --json)
         ///
-        /// This is an optional flag: --json
-        #[arg(long)]
-        json: bool,
+        /// #[command(flatten)] pulls in all the flags defined on OutputArgs,
+        /// so every subcommand shares them without repeating the definitions
+        #[command(flatten)]
+        output: OutputArgs,
+
+        /// Link checking options (--accept, --fail-on, ...)
+        #[command(flatten)]
+        check: CheckArgs,
 
         /// Maximum crawl depth (default: 1)
         ///
@@ -96,6 +138,534 @@ pub enum Commands {
         #[arg(long, short = 'c', default_value_t = 500)]
         concurrency: usize,
     },
+
+    /// Check a plain list of URLs, one per line
+    ///
+    /// Blank lines and lines starting with # are skipped
+    /// Example: grep -o 'https://[^"]*' export.csv | link-guardian urls -
+    Urls {
+        /// File with one URL per line, or - to read from stdin
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Output options (--format, --json)
+        #[command(flatten)]
+        output: OutputArgs,
+
+        /// Link checking options (--accept, --fail-on, ...)
+        #[command(flatten)]
+        check: CheckArgs,
+
+        /// Number of concurrent link checks (default: 500)
+        ///
+        /// Higher values = faster checking but may trigger rate limits
+        /// Example: --concurrency 1000
+        #[arg(long, short = 'c', default_value_t = 500)]
+        concurrency: usize,
+    },
+
+    /// Show step by step how one link is checked (DNS, TLS, redirects, ...)
+    ///
+    /// Example: link-guardian explain https://example.com/old-page
+    Explain {
+        /// The URL to explain
+        url: String,
+
+        /// Link checking options (--accept, --fail-on, ...)
+        #[command(flatten)]
+        check: ExplainArgs,
+    },
+
+    /// Render saved JSON reports in another format
+    ///
+    /// Several reports (e.g. shards from parallel CI jobs) are merged into
+    /// one, with each URL listed once
+    /// Example: link-guardian report shard-*.json --format html -o report.html
+    Report {
+        /// JSON reports saved with --format json (or ndjson)
+        #[arg(required = true, value_name = "REPORT")]
+        reports: Vec<PathBuf>,
+
+        /// Output options (--format, --output, ...)
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// Compare two JSON reports and list what changed
+    ///
+    /// Exits with 1 if links broke since the old report
+    /// Example: link-guardian diff last-week.json today.json
+    Diff {
+        /// The older report (saved with --format json)
+        before: PathBuf,
+
+        /// The newer report
+        after: PathBuf,
+
+        /// Print the changes as JSON instead of text
+        #[arg(long)]
+        json: bool,
+
+        /// Write the changes to a file instead of printing them
+        #[arg(long = "output", short = 'o', value_name = "FILE")]
+        output_file: Option<PathBuf>,
+    },
+
+    /// Inspect or clean up the result cache used by --cache
+    ///
+    /// Example: link-guardian cache prune
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommand,
+    },
+}
+
+// What to do with the result cache
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// List cached results with their age and whether they are still fresh
+    Show {
+        #[command(flatten)]
+        cache: CacheArgs,
+    },
+    /// Remove results that are older than their time-to-live
+    Prune {
+        #[command(flatten)]
+        cache: CacheArgs,
+    },
+    /// Remove every cached result
+    Clear {
+        #[command(flatten)]
+        cache: CacheArgs,
+    },
+}
+
+// The output formats we support
+//
+// #[derive(ValueEnum)] lets clap parse "--format sarif" into OutputFormat::Sarif
+// and list the possible values in --help
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable table (default)
+    Table,
+    /// JSON array of results
+    Json,
+    /// SARIF 2.1.0, for GitHub code scanning and other dashboards
+    Sarif,
+    /// JUnit XML, for the test report UI of Jenkins, GitLab, etc.
+    Junit,
+    /// Self-contained HTML page (use with --output report.html)
+    Html,
+    /// Markdown, for pull request comments and $GITHUB_STEP_SUMMARY
+    Markdown,
+    /// GitHub Actions annotations (default when GITHUB_ACTIONS=true)
+    Github,
+    /// CSV, one row per link and location, for spreadsheets
+    Csv,
+    /// Newline-delimited JSON, one result per line as each check completes
+    Ndjson,
+}
+
+// Flags that control how links are checked and what fails the run
+#[derive(Args, Debug)]
+pub struct CheckArgs {
+    /// Accepted status codes and what fails the run
+    #[command(flatten)]
+    pub policy: PolicyArgs,
+
+    /// Only fail on failures that aren't recorded in this file
+    ///
+    /// If the file doesn't exist, the current failures are recorded in it
+    /// Example: --baseline links-baseline.json
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Remove links that no longer fail from the --baseline file
+    #[arg(long, requires = "baseline")]
+    pub update_baseline: bool,
+
+    /// Reuse recent results from earlier runs instead of checking again
+    ///
+    /// Implied by --cache-file
+    #[arg(long)]
+    pub cache: bool,
+
+    /// Where the cache lives and how long results stay fresh
+    #[command(flatten)]
+    pub cache_settings: CacheArgs,
+
+    /// Extra headers, cookies and credentials for requests
+    #[command(flatten)]
+    pub request: RequestArgs,
+
+    /// User-Agent, timeouts and redirect limit
+    #[command(flatten)]
+    pub client: ClientArgs,
+}
+
+impl CheckArgs {
+    // The checker settings these flags describe
+    //
+    // Fails if --cache is on and the cache location can't be determined.
+    pub fn options(&self) -> Result<CheckOptions> {
+        let cache = if self.cache || self.cache_settings.cache_file.is_some() {
+            Some(self.cache_settings.open()?.shared())
+        } else {
+            None
+        };
+
+        Ok(CheckOptions {
+            accept: self.policy.accept(),
+            cache,
+            headers: self.request.headers(),
+            client: self.client.settings(),
+        })
+    }
+
+    // Which statuses fail the run
+    pub fn failure_policy(&self) -> FailurePolicy {
+        self.policy.failure_policy()
+    }
+}
+
+// The flags of `explain`
+//
+// Like CheckArgs without the cache and --baseline: the one link is always
+// checked again, and there is no earlier run to compare it with.
+#[derive(Args, Debug)]
+pub struct ExplainArgs {
+    /// Accepted status codes and what fails the run
+    #[command(flatten)]
+    pub policy: PolicyArgs,
+
+    /// Extra headers, cookies and credentials for requests
+    #[command(flatten)]
+    pub request: RequestArgs,
+
+    /// User-Agent, timeouts and redirect limit
+    #[command(flatten)]
+    pub client: ClientArgs,
+}
+
+impl ExplainArgs {
+    // The checker settings these flags describe
+    pub fn options(&self) -> CheckOptions {
+        CheckOptions {
+            accept: self.policy.accept(),
+            cache: None,
+            headers: self.request.headers(),
+            client: self.client.settings(),
+        }
+    }
+}
+
+// Flags for which status codes are OK and which statuses fail the run
+#[derive(Args, Debug)]
+pub struct PolicyArgs {
+    /// HTTP status codes that count as OK [default: 200-299]
+    ///
+    /// Example: --accept 200-299,403,429
+    #[arg(long, value_name = "CODES")]
+    pub accept: Option<StatusCodes>,
+
+    /// Accepted status codes for one domain and its subdomains (repeatable)
+    ///
+    /// Example: --accept-for intranet.example.com=200-299,401,403
+    #[arg(long, value_name = "DOMAIN=CODES")]
+    pub accept_for: Vec<ForDomain<StatusCodes>>,
+
+    /// Only these statuses make the exit code 1 [default: all but ok/redirect]
+    ///
+    /// Example: --fail-on broken,dns_error
+    #[arg(long, value_name = "STATUSES", value_delimiter = ',', value_parser = status_names())]
+    pub fail_on: Option<Vec<String>>,
+
+    /// Statuses that are reported as warnings but never fail the run
+    ///
+    /// Example: --warn-on redirect,timeout
+    #[arg(long, value_name = "STATUSES", value_delimiter = ',', value_parser = status_names())]
+    pub warn_on: Vec<String>,
+}
+
+impl PolicyArgs {
+    // Which status codes count as OK, globally and per domain
+    pub fn accept(&self) -> AcceptPolicy {
+        AcceptPolicy {
+            default: self.accept.clone().unwrap_or_default(),
+            per_domain: self.accept_for.clone(),
+        }
+    }
+
+    // Which statuses fail the run
+    pub fn failure_policy(&self) -> FailurePolicy {
+        FailurePolicy {
+            fail_on: self.fail_on.clone(),
+            warn_on: self.warn_on.clone(),
+            ..FailurePolicy::default()
+        }
+    }
+}
+
+// The status names --fail-on and --warn-on accept
+//
+// Same names as in the JSON output; "dns" and "ssl" work as short forms
+// and are turned into the full name ("dns_error").
+fn status_names() -> impl TypedValueParser<Value = String> {
+    let short = |name: &'static str| name.strip_suffix("_error").filter(|s| !s.is_empty());
+
+    PossibleValuesParser::new(LinkStatus::NAMES.iter().map(move |name| match short(name) {
+        Some(alias) => PossibleValue::new(*name).alias(alias),
+        None => PossibleValue::new(*name),
+    }))
+    .map(move |value| {
+        LinkStatus::NAMES
+            .iter()
+            .find(|name| **name == value || short(name) == Some(value.as_str()))
+            .map_or(value.clone(), |name| name.to_string())
+    })
+}
+
+// Flags that add headers, cookies and credentials to requests
+//
+// Sent with link checks and with the crawler's page requests. Values
+// starting with env: are read from that environment variable, e.g.
+// --bearer-for wiki.example.com=env:WIKI_TOKEN
+#[derive(Args, Debug)]
+pub struct RequestArgs {
+    /// Extra header for every request (repeatable)
+    ///
+    /// Example: --header "Accept-Language: en"
+    #[arg(long = "header", value_name = "NAME: VALUE")]
+    pub headers: Vec<Header>,
+
+    /// Extra header for one domain and its subdomains (repeatable)
+    ///
+    /// Example: --header-for docs.example.com="X-Api-Key: env:DOCS_KEY"
+    #[arg(long, value_name = "DOMAIN=NAME: VALUE")]
+    pub header_for: Vec<ForDomain<Header>>,
+
+    /// Cookie for every request (repeatable)
+    ///
+    /// Example: --cookie consent=yes
+    #[arg(long = "cookie", value_name = "NAME=VALUE")]
+    pub cookies: Vec<Cookie>,
+
+    /// Cookie for one domain and its subdomains (repeatable)
+    ///
+    /// Example: --cookie-for wiki.example.com=session=env:WIKI_SESSION
+    #[arg(long, value_name = "DOMAIN=NAME=VALUE")]
+    pub cookie_for: Vec<ForDomain<Cookie>>,
+
+    /// Basic authentication for one domain and its subdomains (repeatable)
+    ///
+    /// Example: --basic-auth-for staging.example.com=ci:env:STAGING_PASSWORD
+    #[arg(long, value_name = "DOMAIN=USER:PASSWORD", value_parser = basic_auth_for)]
+    pub basic_auth_for: Vec<ForDomain<Credentials>>,
+
+    /// Bearer token for one domain and its subdomains (repeatable)
+    ///
+    /// Example: --bearer-for wiki.example.com=env:WIKI_TOKEN
+    #[arg(long, value_name = "DOMAIN=TOKEN", value_parser = bearer_for)]
+    pub bearer_for: Vec<ForDomain<Credentials>>,
+}
+
+impl RequestArgs {
+    // The headers these flags describe
+    pub fn headers(&self) -> RequestHeaders {
+        RequestHeaders {
+            headers: self.headers.clone(),
+            headers_for: self.header_for.clone(),
+            cookies: self.cookies.clone(),
+            cookies_for: self.cookie_for.clone(),
+            credentials: self.basic_auth_for.iter().chain(&self.bearer_for).cloned().collect(),
+        }
+    }
+}
+
+// Flags for the HTTP clients used to check links and crawl
+//
+// Every setting can be overridden for one domain and its subdomains, e.g.
+// --timeout-for legacy.example.com=60s. The proxy and CA certificates are
+// the same for every domain.
+#[derive(Args, Debug)]
+pub struct ClientArgs {
+    /// User-Agent header sent with every request
+    #[arg(long, value_name = "AGENT", default_value = DEFAULT_USER_AGENT)]
+    pub user_agent: UserAgent,
+
+    /// Time allowed to connect, including the TLS handshake
+    #[arg(long, value_name = "DURATION", default_value = "10s")]
+    pub connect_timeout: Timeout,
+
+    /// Time allowed for a whole request
+    #[arg(long, value_name = "DURATION", default_value = "10s")]
+    pub timeout: Timeout,
+
+    /// How many redirects are followed before a link is too_many_redirects
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    pub max_redirects: usize,
+
+    /// User-Agent for one domain (repeatable)
+    ///
+    /// Example: --user-agent-for picky.example.com="Mozilla/5.0 (compatible)"
+    #[arg(long, value_name = "DOMAIN=AGENT")]
+    pub user_agent_for: Vec<ForDomain<UserAgent>>,
+
+    /// Connect timeout for one domain (repeatable)
+    #[arg(long, value_name = "DOMAIN=DURATION")]
+    pub connect_timeout_for: Vec<ForDomain<Timeout>>,
+
+    /// Request timeout for one domain (repeatable)
+    ///
+    /// Example: --timeout-for legacy.example.com=60s
+    #[arg(long, value_name = "DOMAIN=DURATION")]
+    pub timeout_for: Vec<ForDomain<Timeout>>,
+
+    /// Redirect limit for one domain (repeatable)
+    #[arg(long, value_name = "DOMAIN=N")]
+    pub max_redirects_for: Vec<ForDomain<usize>>,
+
+    /// Send every request through this proxy [default: HTTPS_PROXY/HTTP_PROXY]
+    ///
+    /// Example: --proxy http://proxy.corp:3128
+    #[arg(long, value_name = "URL")]
+    pub proxy: Option<ProxyUrl>,
+
+    /// Comma-separated hosts that bypass the proxy [default: NO_PROXY]
+    ///
+    /// Example: --no-proxy localhost,.corp.example.com,10.0.0.0/8
+    #[arg(long, value_name = "HOSTS")]
+    pub no_proxy: Option<String>,
+
+    /// Also trust the CA certificates in this PEM file (repeatable)
+    ///
+    /// Example: --ca-cert /etc/ssl/certs/corp-root.pem
+    #[arg(long, value_name = "FILE")]
+    pub ca_cert: Vec<CaBundle>,
+}
+
+impl ClientArgs {
+    // The client settings these flags describe
+    pub fn settings(&self) -> ClientSettings {
+        ClientSettings {
+            user_agent: self.user_agent.clone(),
+            connect_timeout: self.connect_timeout,
+            timeout: self.timeout,
+            max_redirects: self.max_redirects,
+            user_agent_for: self.user_agent_for.clone(),
+            connect_timeout_for: self.connect_timeout_for.clone(),
+            timeout_for: self.timeout_for.clone(),
+            max_redirects_for: self.max_redirects_for.clone(),
+            network: Arc::new(Network {
+                proxy: self.proxy.clone(),
+                no_proxy: self.no_proxy.clone(),
+                ca_certs: self.ca_cert.clone(),
+            }),
+        }
+    }
+}
+
+// Parses --basic-auth-for DOMAIN=USER:PASSWORD
+fn basic_auth_for(text: &str) -> Result<ForDomain<Credentials>, String> {
+    let entry: ForDomain<String> = text.parse()?;
+    Ok(ForDomain {
+        domain: entry.domain,
+        value: Credentials::basic(&entry.value)?,
+    })
+}
+
+// Parses --bearer-for DOMAIN=TOKEN
+fn bearer_for(text: &str) -> Result<ForDomain<Credentials>, String> {
+    let entry: ForDomain<Secret> = text.parse()?;
+    Ok(ForDomain {
+        domain: entry.domain,
+        value: Credentials::Bearer(entry.value),
+    })
+}
+
+// Flags that locate the result cache and set its time-to-live
+//
+// Shared by the scan subcommands (with --cache) and `link-guardian cache`.
+#[derive(Args, Debug)]
+pub struct CacheArgs {
+    /// Cache file [default: link-guardian/results.json in the user cache dir]
+    ///
+    /// Example: --cache-file .link-cache.json (e.g. to keep it in CI caches)
+    #[arg(long, value_name = "FILE")]
+    pub cache_file: Option<PathBuf>,
+
+    /// How long results with a status are reused (repeatable)
+    /// [default: ok=24h, redirect=24h, failures are never cached]
+    ///
+    /// Example: --cache-ttl ok=7d --cache-ttl forbidden=1h
+    #[arg(long, value_name = "STATUS=DURATION")]
+    pub cache_ttl: Vec<StatusTtl>,
+}
+
+impl CacheArgs {
+    // Loads the cache these flags point at
+    pub fn open(&self) -> Result<ResultCache> {
+        let path = match &self.cache_file {
+            Some(path) => path.clone(),
+            None => ResultCache::default_path()?,
+        };
+        ResultCache::load(path, CacheTtls::new(&self.cache_ttl))
+    }
+}
+
+impl OutputFormat {
+    // True for formats meant for other programs rather than people
+    //
+    // Progress bars are never drawn for these, even on a terminal.
+    pub fn is_structured(self) -> bool {
+        self != OutputFormat::Table
+    }
+}
+
+// Flags that control how results are printed
+//
+// #[derive(Args)] makes this a group of arguments that can be flattened
+// into any subcommand
+#[derive(Args, Debug)]
+pub struct OutputArgs {
+    /// Output results in JSON format instead of a table
+    ///
+    /// Shorthand for --format json
+    #[arg(long, conflicts_with = "format")]
+    pub json: bool,
+
+    /// Output format [default: table, or github inside GitHub Actions]
+    ///
+    /// Example: --format sarif > results.sarif
+    #[arg(long, value_enum)]
+    pub format: Option<OutputFormat>,
+
+    /// Write the report to a file instead of printing it
+    ///
+    /// Example: --format html --output report.html
+    #[arg(long = "output", short = 'o', value_name = "FILE")]
+    pub output_file: Option<PathBuf>,
+
+    /// Only list failing links in the table (the summary still counts all)
+    #[arg(long)]
+    pub failures_only: bool,
+}
+
+impl OutputArgs {
+    // The format to use, taking the --json shorthand into account
+    //
+    // Without --format we print GitHub annotations when running inside
+    // GitHub Actions (which sets GITHUB_ACTIONS=true) and a table otherwise.
+    pub fn format(&self) -> OutputFormat {
+        if self.json {
+            return OutputFormat::Json;
+        }
+        match self.format {
+            Some(format) => format,
+            None if std::env::var("GITHUB_ACTIONS").as_deref() == Ok("true") => OutputFormat::Github,
+            None => OutputFormat::Table,
+        }
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -125,3 +695,152 @@ pub enum Commands {
 //    - Used for sizes, lengths, and indices
 //    - On 64-bit systems, usize is 64 bits
 // -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fail_on_short_names() {
+        let cli = Cli::try_parse_from(["link-guardian", "site", "https://example.com", "--fail-on", "dns,broken"])
+            .unwrap();
+        match cli.command {
+            Commands::Site { check, .. } => {
+                assert_eq!(check.policy.fail_on, Some(vec!["dns_error".to_string(), "broken".to_string()]));
+            }
+            _ => panic!("expected the site subcommand"),
+        }
+    }
+
+    #[test]
+    fn test_explain_flags() {
+        let cli = Cli::try_parse_from(["link-guardian", "explain", "https://a.com", "--accept", "200-299,403"]).unwrap();
+        match cli.command {
+            Commands::Explain { check, .. } => assert!(check.options().cache.is_none()),
+            _ => panic!("expected the explain subcommand"),
+        }
+
+        // explain always checks the link again, so these make no sense there
+        for flag in ["--cache", "--cache-ttl=ok=1h", "--baseline=b.json", "--update-baseline"] {
+            assert!(Cli::try_parse_from(["link-guardian", "explain", "https://a.com", flag]).is_err(), "{}", flag);
+        }
+    }
+
+    #[test]
+    fn test_cache_flags() {
+        let cli = Cli::try_parse_from([
+            "link-guardian",
+            "github",
+            "https://github.com/o/r",
+            "--cache-ttl",
+            "ok=7d",
+            "--cache-ttl",
+            "redirect=0",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Github { check, .. } => {
+                assert!(!check.cache);
+                assert_eq!(check.cache_settings.cache_ttl.len(), 2);
+                assert!(check.options().unwrap().cache.is_none());
+            }
+            _ => panic!("expected the github subcommand"),
+        }
+
+        assert!(Cli::try_parse_from(["link-guardian", "cache", "prune", "--cache-ttl", "ok=1h"]).is_ok());
+        assert!(Cli::try_parse_from(["link-guardian", "cache", "show", "--cache-ttl", "ok=later"]).is_err());
+    }
+
+    #[test]
+    fn test_request_flags() {
+        let cli = Cli::try_parse_from([
+            "link-guardian",
+            "site",
+            "https://example.com",
+            "--header",
+            "Accept-Language: en",
+            "--basic-auth-for",
+            "staging.example.com=ci:pa:ss",
+            "--bearer-for",
+            "wiki.example.com=token",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Site { check, .. } => {
+                let headers = check.options().unwrap().headers;
+                assert_eq!(headers.headers.len(), 1);
+                assert_eq!(headers.credentials.len(), 2);
+                assert_eq!(headers.credentials[0].domain, "staging.example.com");
+            }
+            _ => panic!("expected the site subcommand"),
+        }
+
+        assert!(Cli::try_parse_from(["link-guardian", "site", "https://e.com", "--bearer-for", "token"]).is_err());
+    }
+
+    #[test]
+    fn test_client_flags() {
+        let cli = Cli::try_parse_from([
+            "link-guardian",
+            "urls",
+            "links.txt",
+            "--timeout",
+            "5s",
+            "--timeout-for",
+            "slow.example.com=2m",
+            "--max-redirects-for",
+            "example.com=10",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Urls { check, .. } => {
+                let settings = check.options().unwrap().client;
+                assert_eq!(settings.user_agent.0, DEFAULT_USER_AGENT);
+                assert_eq!(settings.for_url("https://a.com").timeout.as_secs(), 5);
+                assert_eq!(settings.for_url("https://slow.example.com").timeout.as_secs(), 120);
+                assert_eq!(settings.for_url("https://slow.example.com").max_redirects, 10);
+            }
+            _ => panic!("expected the urls subcommand"),
+        }
+
+        assert!(Cli::try_parse_from(["link-guardian", "urls", "-", "--timeout", "0"]).is_err());
+        assert!(Cli::try_parse_from(["link-guardian", "urls", "-", "--max-redirects-for", "a.com=many"]).is_err());
+        assert!(Cli::try_parse_from(["link-guardian", "urls", "-", "--user-agent", "bad\nagent"]).is_err());
+        assert!(Cli::try_parse_from(["link-guardian", "urls", "-", "--user-agent-for", "a.com=bad\x7fagent"]).is_err());
+    }
+
+    #[test]
+    fn test_network_flags() {
+        let cli = Cli::try_parse_from([
+            "link-guardian",
+            "urls",
+            "-",
+            "--proxy",
+            "http://proxy.corp:3128",
+            "--no-proxy",
+            "localhost,.corp",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Urls { check, .. } => {
+                let network = check.options().unwrap().client.network;
+                assert_eq!(network.proxy, Some("http://proxy.corp:3128".parse().unwrap()));
+                assert_eq!(network.no_proxy.as_deref(), Some("localhost,.corp"));
+                assert!(network.ca_certs.is_empty());
+            }
+            _ => panic!("expected the urls subcommand"),
+        }
+
+        // --no-proxy also works with a proxy from the environment
+        let cli = Cli::try_parse_from(["link-guardian", "urls", "-", "--no-proxy", "localhost"]).unwrap();
+        match cli.command {
+            Commands::Urls { check, .. } => {
+                let network = check.options().unwrap().client.network;
+                assert_eq!(network.proxy, None);
+                assert_eq!(network.no_proxy.as_deref(), Some("localhost"));
+            }
+            _ => panic!("expected the urls subcommand"),
+        }
+        assert!(Cli::try_parse_from(["link-guardian", "urls", "-", "--ca-cert", "/nonexistent.pem"]).is_err());
+    }
+}
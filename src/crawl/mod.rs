@@ -7,6 +7,7 @@
 // - Respects same-domain restriction (doesn't crawl external sites)
 // - Configurable depth limit
 // - Polite crawling with delays between requests
+// - Downloads same-site stylesheets so CSS url() references get checked
 //
 // Why crawl?
 // - To find all pages on a website
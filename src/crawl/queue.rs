@@ -8,6 +8,13 @@
 // 3. Extract all links from the page
 // 4. Add same-domain links to the queue (if not visited and within depth limit)
 // 5. Repeat until queue is empty or max depth reached
+// 6. Download same-site stylesheets used by the crawled pages (and the
+//    stylesheets they @import) so their url() references can be checked
+//
+// With --cache, pages are fetched with If-None-Match / If-Modified-Since
+// and a 304 Not Modified answer reuses the copy from the last crawl.
+// Headers, cookies and credentials from --header, --bearer-for etc. are
+// sent with every page request, so pages behind a login can be crawled.
 //
 // Politeness:
 // - Adds delay between requests to avoid overwhelming servers
@@ -20,11 +27,28 @@
 // =============================================================================
 
 use anyhow::{anyhow, Result};
-use reqwest::Client;
+use reqwest::StatusCode;
 use scraper::{Html, Selector};
 use std::collections::{HashSet, VecDeque};
+use std::time::Instant;
+use tracing::{debug, info_span, warn, Instrument};
 use url::Url;
 
+use crate::cache::Validators;
+use crate::checker::{self, send_following, CheckOptions, Clients};
+use crate::progress::Progress;
+
+// Everything the crawler downloaded
+//
+// Both lists hold (url, content) tuples.
+#[derive(Debug, Default)]
+pub struct CrawlResult {
+    /// HTML pages, in the order they were crawled
+    pub pages: Vec<(String, String)>,
+    /// Same-site CSS files referenced by those pages
+    pub stylesheets: Vec<(String, String)>,
+}
+
 // Represents a page in the crawl queue
 #[derive(Debug, Clone)]
 struct CrawlItem {
@@ -37,14 +61,23 @@ struct CrawlItem {
 // Parameters:
 //   start_url: The URL to start crawling from
 //   max_depth: Maximum crawl depth (1 = just the starting page)
+//   progress: Updated after every page (pages fetched / still queued)
+//   options: The cache with copies of pages from earlier crawls (--cache,
+//            for 304 answers) and extra headers/credentials (--header, ...)
 //
-// Returns: Vec of (url, html_content) tuples for all crawled pages
+// Returns: CrawlResult with (url, content) tuples for all crawled pages
+//          and the same-site stylesheets they use
 //
 // Example:
 //   max_depth=1: Only crawl the starting page
 //   max_depth=2: Crawl starting page + all pages it links to
 //   max_depth=3: ... + all pages those link to
-pub async fn crawl_website(start_url: &str, max_depth: usize) -> Result<Vec<(String, String)>> {
+pub async fn crawl_website(
+    start_url: &str,
+    max_depth: usize,
+    progress: &Progress,
+    options: &CheckOptions,
+) -> Result<CrawlResult> {
     // Parse and validate the starting URL
     let start = Url::parse(start_url)
         .map_err(|e| anyhow!("Invalid URL '{}': {}", start_url, e))?;
@@ -54,10 +87,8 @@ pub async fn crawl_website(start_url: &str, max_depth: usize) -> Result<Vec<(Str
     let base_domain = start.domain()
         .ok_or_else(|| anyhow!("URL has no domain: {}", start_url))?;
 
-    // Create HTTP client
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .build()?;
+    // HTTP clients with the User-Agent, timeouts etc. of the link checks
+    let clients = Clients::new(options.client.clone(), options.headers.clone());
 
     // Queue of pages to crawl
     // VecDeque allows efficient push/pop from both ends
@@ -73,6 +104,10 @@ pub async fn crawl_website(start_url: &str, max_depth: usize) -> Result<Vec<(Str
     // Store results: (url, html_content)
     let mut results = Vec::new();
 
+    // Same-site stylesheets found on the crawled pages
+    // These are fetched after the pages, whatever their depth
+    let mut stylesheet_queue = VecDeque::new();
+
     // Process the queue until empty
     while let Some(item) = queue.pop_front() {
         // Skip if already visited
@@ -83,11 +118,19 @@ pub async fn crawl_website(start_url: &str, max_depth: usize) -> Result<Vec<(Str
         // Mark as visited
         visited.insert(item.url.clone());
 
-        println!("  Crawling [depth {}]: {}", item.depth, item.url);
+        debug!("  Crawling [depth {}]: {}", item.depth, item.url);
 
-        // Fetch the page
-        match fetch_page(&client, &item.url).await {
+        // Fetch the page inside a "page" span, so its log lines say which page
+        let span = info_span!("page", url = %item.url, depth = item.depth);
+        match fetch_page(&clients, &item.url, options).instrument(span).await {
             Ok(html) => {
+                // Remember same-site stylesheets for later
+                for sheet in checker::extract_stylesheet_links(&html, &item.url) {
+                    if is_same_domain(&sheet, base_domain) {
+                        stylesheet_queue.push_back(sheet);
+                    }
+                }
+
                 // Store the result
                 results.push((item.url.clone(), html.clone()));
 
@@ -111,23 +154,111 @@ pub async fn crawl_website(start_url: &str, max_depth: usize) -> Result<Vec<(Str
                 tokio::time::sleep(std::time::Duration::from_millis(100)).await;
             }
             Err(e) => {
-                eprintln!("  Warning: Failed to fetch {}: {}", item.url, e);
+                warn!("Failed to fetch {}: {}", item.url, e);
+            }
+        }
+
+        progress.page_fetched(visited.len(), queue.len());
+    }
+
+    let stylesheets = fetch_stylesheets(&clients, stylesheet_queue, base_domain, options).await;
+
+    Ok(CrawlResult {
+        pages: results,
+        stylesheets,
+    })
+}
+
+// Downloads stylesheets, following same-site @import rules
+//
+// Returns: Vec of (url, css_content) tuples
+async fn fetch_stylesheets(
+    clients: &Clients,
+    mut queue: VecDeque<String>,
+    base_domain: &str,
+    options: &CheckOptions,
+) -> Vec<(String, String)> {
+    let mut visited = HashSet::new();
+    let mut stylesheets = Vec::new();
+
+    while let Some(url) = queue.pop_front() {
+        if !visited.insert(url.clone()) {
+            continue;
+        }
+
+        debug!("  Fetching stylesheet: {}", url);
+
+        let span = info_span!("stylesheet", url = %url);
+        match fetch_page(clients, &url, options).instrument(span).await {
+            Ok(css) => {
+                for import in checker::extract_css_imports(&css, &url) {
+                    if is_same_domain(&import, base_domain) && !visited.contains(&import) {
+                        queue.push_back(import);
+                    }
+                }
+                stylesheets.push((url, css));
+
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+            Err(e) => {
+                // The broken stylesheet itself is reported by the link check
+                warn!("Failed to fetch stylesheet {}: {}", url, e);
             }
         }
     }
 
-    Ok(results)
+    stylesheets
+}
+
+// Checks if an absolute URL is HTTP/HTTPS on the given domain
+fn is_same_domain(url: &str, base_domain: &str) -> bool {
+    match Url::parse(url) {
+        Ok(parsed) => {
+            (parsed.scheme() == "http" || parsed.scheme() == "https")
+                && parsed.domain() == Some(base_domain)
+        }
+        Err(_) => false,
+    }
 }
 
 // Fetches a web page and returns its HTML content
-async fn fetch_page(client: &Client, url: &str) -> Result<String> {
-    let response = client.get(url).send().await?;
+//
+// With a cache, the request is conditional when we have a copy of the
+// page: 304 Not Modified returns the copy, anything new replaces it.
+async fn fetch_page(clients: &Clients, url: &str, options: &CheckOptions) -> Result<String> {
+    let cache = options.cache.as_ref();
+    let validators = cache.and_then(|cache| cache.lock().unwrap().page_validators(url));
+
+    let started = Instant::now();
+    let client = clients.for_url(url);
+    let get = |target: &str| {
+        let request = options.headers.apply(target, client.get(target));
+        match &validators {
+            Some(validators) if target == url => validators.apply(request),
+            _ => request,
+        }
+    };
+    let response = send_following(url, options, get)
+        .await?
+        .ok_or_else(|| anyhow!("too many redirects"))?;
+    debug!("GET -> {} in {} ms", response.status(), started.elapsed().as_millis());
+
+    if validators.is_some() && response.status() == StatusCode::NOT_MODIFIED {
+        if let Some(html) = cache.and_then(|cache| cache.lock().unwrap().revalidate_page(url)) {
+            debug!("not modified, using the cached copy");
+            return Ok(html);
+        }
+    }
 
     if !response.status().is_success() {
         return Err(anyhow!("HTTP {}", response.status()));
     }
 
+    let validators = Validators::from_headers(response.headers());
     let html = response.text().await?;
+    if let Some(cache) = cache {
+        cache.lock().unwrap().insert_page(url, validators, &html);
+    }
     Ok(html)
 }
 
@@ -164,16 +295,11 @@ fn extract_same_domain_links(html: &str, page_url: &str, base_domain: &str) -> V
                 None => continue,
             };
 
-            // Check if it's on the same domain
-            if let Ok(parsed) = Url::parse(&absolute_url) {
-                // Only include if:
-                // 1. It's HTTP/HTTPS
-                // 2. It's on the same domain
-                if (parsed.scheme() == "http" || parsed.scheme() == "https")
-                    && parsed.domain() == Some(base_domain)
-                {
-                    links.push(absolute_url);
-                }
+            // Only include if:
+            // 1. It's HTTP/HTTPS
+            // 2. It's on the same domain
+            if is_same_domain(&absolute_url, base_domain) {
+                links.push(absolute_url);
             }
         }
     }
@@ -278,4 +404,11 @@ mod tests {
         let result = resolve_link(&base, "mailto:test@example.com");
         assert_eq!(result, None);
     }
+
+    #[test]
+    fn test_is_same_domain() {
+        assert!(is_same_domain("https://example.com/css/site.css", "example.com"));
+        assert!(!is_same_domain("https://cdn.example.net/site.css", "example.com"));
+        assert!(!is_same_domain("ftp://example.com/file", "example.com"));
+    }
 }
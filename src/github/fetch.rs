@@ -5,6 +5,8 @@
 // Strategy:
 // - Parse the GitHub URL to extract owner and repo name
 // - Fetch README.md from the repo root
+// - List the repository tree (one GitHub API call) to find Jupyter notebooks
+//   and, optionally, source files whose comments should be checked
 // - Use raw.githubusercontent.com which serves raw file contents
 //
 // Why not the GitHub API?
@@ -20,12 +22,39 @@
 // =============================================================================
 
 use anyhow::{anyhow, Result};
+use futures::stream::{self, StreamExt};
 use reqwest::Client;
+use serde::Deserialize;
+use tracing::warn;
+use url::Url;
+
+use crate::checker;
+
+// How many files to download from raw.githubusercontent.com at once
+const FILE_FETCH_CONCURRENCY: usize = 16;
+
+// The parts of GitHub's "get a tree" API response we care about
+// https://docs.github.com/en/rest/git/trees#get-a-tree
+#[derive(Debug, Deserialize)]
+struct TreeResponse {
+    tree: Vec<TreeEntry>,
+    #[serde(default)]
+    truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TreeEntry {
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,  // "blob" for files, "tree" for directories
+}
 
 // Fetches markdown files from a GitHub repository
 //
 // Parameters:
 //   repo_url: GitHub repository URL (e.g., "https://github.com/rust-lang/rust")
+//   include_source: also fetch source files so links in comments can be checked
+//   network: proxy and extra CA certificates (--proxy, --ca-cert)
 //
 // Returns: Result<Vec<(String, String)>>
 //   Success: Vec of (filename, content) tuples
@@ -33,13 +62,19 @@ use reqwest::Client;
 //
 // Currently fetches:
 //   - README.md from repo root
+//   - Every Jupyter notebook (*.ipynb) in the repository
+//   - Source files (*.rs, *.py, *.js, ...) when include_source is true
 //   - (Future: files from docs/ directory)
-pub async fn fetch_repo_files(repo_url: &str) -> Result<Vec<(String, String)>> {
+pub async fn fetch_repo_files(
+    repo_url: &str,
+    include_source: bool,
+    network: &checker::Network,
+) -> Result<Vec<(String, String)>> {
     // Parse the URL to extract owner and repo name
     let (owner, repo) = parse_github_url(repo_url)?;
 
-    // Create HTTP client for making requests
-    let client = Client::new();
+    // Create HTTP client for making requests (through the proxy, if any)
+    let client = network.configure(Client::builder()).build()?;
 
     let mut files = Vec::new();
 
@@ -67,18 +102,125 @@ pub async fn fetch_repo_files(repo_url: &str) -> Result<Vec<(String, String)>> {
                     files.push(("README.md".to_string(), content));
                 }
                 Err(e) => {
-                    eprintln!("Warning: Could not fetch README.md: {}", e);
+                    warn!("Could not fetch README.md: {}", e);
                 }
             }
         }
     }
 
+    // Notebooks (and source files) can live anywhere in the repo, so we need
+    // the file listing. This is a single unauthenticated API call; if it fails
+    // (e.g. rate limited) we still return the README rather than failing
+    match list_repo_paths(&client, &owner, &repo).await {
+        Ok(paths) => {
+            let wanted: Vec<String> = paths
+                .into_iter()
+                .filter(|p| is_notebook(p) || (include_source && checker::is_source_file(p)))
+                .collect();
+
+            // Download the files concurrently - repos can have hundreds of them
+            let fetches = wanted.into_iter().map(|path| {
+                let client = client.clone();
+                let url = raw_file_url(&owner, &repo, &path);
+                async move {
+                    let content = match url {
+                        Ok(url) => fetch_file(&client, &url).await,
+                        Err(e) => Err(e),
+                    };
+                    (path, content)
+                }
+            });
+            let fetched: Vec<_> = stream::iter(fetches)
+                .buffered(FILE_FETCH_CONCURRENCY)
+                .collect()
+                .await;
+
+            for (path, content) in fetched {
+                match content {
+                    Ok(content) => files.push((path, content)),
+                    Err(e) => warn!("Could not fetch {}: {}", path, e),
+                }
+            }
+        }
+        Err(e) => {
+            warn!("Could not list repository files, skipping notebooks: {}", e);
+        }
+    }
+
     // Future enhancement: Also fetch from docs/ directory
-    // Would require using GitHub API to list directory contents
 
     Ok(files)
 }
 
+// Returns true for Jupyter notebook files
+pub fn is_notebook(path: &str) -> bool {
+    path.ends_with(".ipynb")
+}
+
+// Builds the github.com URL a file is displayed at
+//
+// Used as the base URL when resolving relative links inside a file.
+//
+// Example:
+//   ("https://github.com/user/repo", "docs/intro.ipynb")
+//     -> "https://github.com/user/repo/blob/HEAD/docs/intro.ipynb"
+pub fn blob_url(repo_url: &str, path: &str) -> Result<String> {
+    let (owner, repo) = parse_github_url(repo_url)?;
+    let mut url = Url::parse("https://github.com")?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow!("Cannot build URL for {}", path))?
+        .extend([owner.as_str(), repo.as_str(), "blob", "HEAD"])
+        .extend(path.split('/'));
+    Ok(url.to_string())
+}
+
+// Lists every file path in the repository's default branch
+//
+// Uses GitHub's tree API with recursive=1, which returns the whole
+// repository in one request ("HEAD" means "the default branch").
+async fn list_repo_paths(client: &Client, owner: &str, repo: &str) -> Result<Vec<String>> {
+    let api_url = format!(
+        "https://api.github.com/repos/{}/{}/git/trees/HEAD?recursive=1",
+        owner, repo
+    );
+
+    // The GitHub API rejects requests without a User-Agent header
+    let response = client
+        .get(&api_url)
+        .header("User-Agent", "link-guardian")
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(anyhow!("GitHub API returned HTTP {}", response.status()));
+    }
+
+    let tree: TreeResponse = response.json().await?;
+    if tree.truncated {
+        warn!("Repository is very large, file listing was truncated by GitHub");
+    }
+
+    Ok(tree
+        .tree
+        .into_iter()
+        .filter(|entry| entry.kind == "blob")
+        .map(|entry| entry.path)
+        .collect())
+}
+
+// Builds the raw.githubusercontent.com URL for a file
+//
+// Path segments are percent-encoded, so files with spaces work too.
+fn raw_file_url(owner: &str, repo: &str, path: &str) -> Result<String> {
+    let mut url = Url::parse("https://raw.githubusercontent.com")?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow!("Cannot build URL for {}", path))?
+        .extend([owner, repo, "HEAD"])
+        .extend(path.split('/'));
+    Ok(url.to_string())
+}
+
 // Parses a GitHub URL to extract owner and repository name
 //
 // Supported formats:
@@ -205,6 +347,15 @@ mod tests {
         assert_eq!(repo, "repo");
     }
 
+    #[test]
+    fn test_raw_file_url_encodes_path() {
+        let url = raw_file_url("user", "repo", "docs/My Notebook.ipynb").unwrap();
+        assert_eq!(
+            url,
+            "https://raw.githubusercontent.com/user/repo/HEAD/docs/My%20Notebook.ipynb"
+        );
+    }
+
     #[test]
     fn test_parse_invalid_url() {
         let result = parse_github_url("https://gitlab.com/user/repo");
@@ -5,6 +5,7 @@
 // Currently implements:
 // - Parsing GitHub URLs to extract owner/repo
 // - Fetching README.md and files from docs/ directory
+// - Finding Jupyter notebooks via the repository tree
 // - Using raw.githubusercontent.com to get file contents
 //
 // Future enhancements (stretch goals):
@@ -20,4 +21,4 @@
 mod fetch;
 
 // Re-export the main function from fetch.rs
-pub use fetch::fetch_repo_files;
+pub use fetch::{blob_url, fetch_repo_files, is_notebook};
@@ -0,0 +1,207 @@
+// src/logging.rs
+// =============================================================================
+// This module sets up logging with the `tracing` crate.
+//
+// stdout is reserved for the report itself, so that
+//   link-guardian site https://example.com --format json > report.json
+// produces a file that any JSON parser can read. Everything else - banners,
+// "Crawling ..." lines, warnings, debug details - is logged with tracing's
+// info!/warn!/debug! macros and printed to stderr.
+//
+// How much is printed:
+//   --quiet   warnings and errors only
+//   (default) progress messages
+//   -v        + debug details (timings, status codes, why a request failed)
+//   -vv       + trace details (response headers)
+//   RUST_LOG  overrides all of the above, e.g. RUST_LOG=link_guardian::crawl=debug
+//
+// Work is grouped into spans: one per scan, one per crawled page and one per
+// link check. With -v every line says which span it came from, e.g.
+//   DEBUG check{url=https://example.com}: HEAD -> 200 OK in 84 ms
+//
+// --log-file FILE additionally writes every event (debug and up) as JSON,
+// one object per line, including the spans it happened in.
+//
+// Rust concepts:
+// - Traits: We implement FormatEvent to control how console lines look
+// - Layers: tracing-subscriber combines several outputs, each with a filter
+// =============================================================================
+
+use anyhow::{Context, Result};
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+use tracing_subscriber::fmt::format::{self, FormatEvent, FormatFields};
+use tracing_subscriber::fmt::{FmtContext, FormattedFields};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::progress;
+
+// Sets up logging for the whole program
+//
+// Must be called once, before anything is logged.
+//
+// Parameters:
+//   quiet: only print warnings and errors
+//   verbose: how many times -v was given (0 = normal output)
+//   log_file: also write JSON logs to this file
+pub fn init(quiet: bool, verbose: u8, log_file: Option<&Path>) -> Result<()> {
+    // RUST_LOG wins over the command-line flags if it is set
+    let console_filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(default_directives(quiet, verbose)));
+
+    let console = tracing_subscriber::fmt::layer()
+        .with_writer(|| StderrWriter)
+        .with_ansi(false)
+        .event_format(ConsoleFormat { show_spans: verbose > 0 })
+        .with_filter(console_filter);
+
+    // The log file always gets the details, whatever the console shows
+    let json_file = match log_file {
+        Some(path) => {
+            let file = File::create(path)
+                .with_context(|| format!("Could not create log file {}", path.display()))?;
+            let filter = EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new(default_directives(false, verbose.max(1))));
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_current_span(true)
+                    .with_span_list(true)
+                    .with_writer(Mutex::new(file))
+                    .with_filter(filter),
+            )
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(console)
+        .with(json_file)
+        .try_init()
+        .context("Could not set up logging")?;
+
+    Ok(())
+}
+
+// Filter directives for the given flags
+//
+// Only our own crate gets chatty; libraries stay at "warn" so -v isn't
+// flooded with connection pool internals.
+fn default_directives(quiet: bool, verbose: u8) -> String {
+    let level = if quiet {
+        LevelFilter::WARN
+    } else {
+        match verbose {
+            0 => LevelFilter::INFO,
+            1 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        }
+    };
+    format!("warn,link_guardian={}", level)
+}
+
+// Writes log lines to stderr without breaking the progress bar
+//
+// The bar is hidden while the line is written and redrawn below it.
+struct StderrWriter;
+
+impl io::Write for StderrWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        progress::suspend(|| io::Write::write(&mut io::stderr(), buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::Write::flush(&mut io::stderr())
+    }
+}
+
+// How log lines look on the terminal
+//
+// Progress messages (INFO) are printed as-is, so normal output looks like
+// it did with println!. Other levels get a prefix, and with -v they also
+// show the spans they happened in before the message.
+struct ConsoleFormat {
+    show_spans: bool,
+}
+
+impl<S, N> FormatEvent<S, N> for ConsoleFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: format::Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let level = *event.metadata().level();
+        match level {
+            Level::ERROR => write!(writer, "Error: ")?,
+            Level::WARN => write!(writer, "Warning: ")?,
+            Level::INFO => {}
+            level => write!(writer, "{:>5} ", level)?,
+        }
+
+        // Progress messages stay readable; everything else says where it came from
+        if self.show_spans && level != Level::INFO {
+            if let Some(scope) = ctx.event_scope() {
+                for span in scope.from_root() {
+                    write!(writer, "{}", span.name())?;
+                    let extensions = span.extensions();
+                    if let Some(fields) = extensions.get::<FormattedFields<N>>() {
+                        if !fields.is_empty() {
+                            write!(writer, "{{{}}}", fields)?;
+                        }
+                    }
+                    write!(writer, ": ")?;
+                }
+            }
+        }
+
+        ctx.field_format().format_fields(writer.by_ref(), event)?;
+        writeln!(writer)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why not just use println! everywhere?
+//    - println! writes to stdout, where the report goes
+//    - Mixing progress lines into JSON or SARIF makes it unparseable
+//    - stderr still shows up in the terminal, but `> file` doesn't capture it
+//
+// 2. What is a span?
+//    - A named period of work, like "checking https://example.com"
+//    - Every event logged inside it is tagged with the span's fields, so
+//      you can see which link a "connection refused" message belongs to
+//
+// 3. What are layers?
+//    - Each layer is one output (terminal, JSON file) with its own filter
+//    - registry().with(a).with(b) sends every event to both
+//    - Option<Layer> is also a layer: None simply does nothing
+//
+// 4. Why Mutex<File>?
+//    - Many tasks log at the same time; the Mutex makes sure two JSON lines
+//      are never written into each other
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_directives() {
+        assert_eq!(default_directives(true, 2), "warn,link_guardian=warn");
+        assert_eq!(default_directives(false, 0), "warn,link_guardian=info");
+        assert_eq!(default_directives(false, 1), "warn,link_guardian=debug");
+        assert_eq!(default_directives(false, 5), "warn,link_guardian=trace");
+    }
+}
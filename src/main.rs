@@ -8,6 +8,9 @@
 // 3. Collect results and print them
 // 4. Exit with proper code (0 = success, 1 = broken links, 2 = error)
 //
+// Only the report is printed to stdout. Progress messages are logged with
+// tracing (see logging.rs), which writes to stderr.
+//
 // Rust concepts used:
 // - async/await: Because we need to make many network requests concurrently
 // - Result<T, E>: For error handling (T = success type, E = error type)
@@ -19,14 +22,23 @@ mod cli;           // src/cli.rs - command-line parsing
 mod checker;       // src/checker/ - link checking logic
 mod github;        // src/github/ - GitHub-specific functionality
 mod crawl;         // src/crawl/ - website crawling logic
+mod report;        // src/report/ - output formats (table, JSON, SARIF, ...)
+mod logging;       // src/logging.rs - tracing setup (stderr + optional JSON file)
+mod progress;      // src/progress.rs - progress bars on stderr
+mod cache;         // src/cache.rs - results reused between runs (--cache)
+mod baseline;      // src/baseline.rs - known failures that don't fail the run
 
 // Import items we need from our modules
-use cli::{Cli, Commands};
+use cli::{CacheCommand, CheckArgs, Cli, Commands, ExplainArgs, OutputArgs, OutputFormat};
 use clap::Parser;  // Parser trait enables the parse() method
+use progress::Progress;
+use tracing::{info, info_span, warn, Instrument};
 
 // anyhow::Result is like std::result::Result but simpler for applications
 // It lets us return any error type with the ? operator
-use anyhow::Result;
+use anyhow::{Context, Result};
+
+use baseline::Baseline;
 
 // The #[tokio::main] attribute transforms our async main into a real main function
 // It creates a tokio runtime and runs our async code inside it
@@ -57,183 +69,435 @@ async fn run() -> Result<i32> {
     // This will automatically handle --help, --version, etc.
     let cli = Cli::parse();
 
+    // Progress messages go to stderr; --quiet and --verbose pick how many
+    logging::init(cli.quiet, cli.verbose, cli.log_file.as_deref())?;
+
     // Match on which subcommand was used
-    // Each branch handles a different command (github, site)
+    // Each branch handles a different command (github, site, urls, explain, report, diff, cache)
     match cli.command {
-        Commands::Github { repo_url, json, concurrency } => {
+        Commands::Github { repo_url, output, check, concurrency, check_comments } => {
             // Call our github scanning function
-            handle_github_scan(&repo_url, json, concurrency).await
+            // The "scan" span tags every log line of this run with the repo
+            let span = info_span!("scan", repo = %repo_url);
+            handle_github_scan(&repo_url, &output, &check, concurrency, check_comments)
+                .instrument(span)
+                .await
         }
-        Commands::Site { website_url, json, max_depth, concurrency } => {
+        Commands::Site { website_url, output, check, max_depth, concurrency } => {
             // Call our website scanning function
-            handle_site_scan(&website_url, json, max_depth, concurrency).await
+            let span = info_span!("scan", site = %website_url);
+            handle_site_scan(&website_url, &output, &check, max_depth, concurrency)
+                .instrument(span)
+                .await
         }
+        Commands::Urls { file, output, check, concurrency } => {
+            let span = info_span!("scan", list = %file.display());
+            handle_url_list(&file, &output, &check, concurrency).instrument(span).await
+        }
+        Commands::Explain { url, check } => handle_explain(&url, &check).await,
+        Commands::Report { reports, output } => handle_report(&reports, &output),
+        Commands::Diff { before, after, json, output_file } => {
+            handle_diff(&before, &after, json, output_file.as_deref())
+        }
+        Commands::Cache { action } => handle_cache(action),
     }
 }
 
 // Handles the 'github' subcommand
 // Parameters:
 //   repo_url: GitHub repository URL (e.g., "https://github.com/user/repo")
-//   json: whether to output JSON format
+//   output: output format and destination (see cli::OutputArgs)
+//   check: accepted status codes and failure policy (see cli::CheckArgs)
 //   concurrency: number of concurrent link checks
-async fn handle_github_scan(repo_url: &str, json: bool, concurrency: usize) -> Result<i32> {
-    println!("🔍 Scanning GitHub repository: {}", repo_url);
-
-    // Fetch README and docs from the repository
-    let files = github::fetch_repo_files(repo_url).await?;
+//   check_comments: also check links in source code comments
+async fn handle_github_scan(
+    repo_url: &str,
+    output: &OutputArgs,
+    check: &CheckArgs,
+    concurrency: usize,
+    check_comments: bool,
+) -> Result<i32> {
+    info!("🔍 Scanning GitHub repository: {}", repo_url);
+    let options = check.options()?;
+
+    // Fetch README, docs, notebooks (and source files if requested)
+    let files = github::fetch_repo_files(repo_url, check_comments, &options.client.network).await?;
 
     if files.is_empty() {
-        println!("⚠️  No markdown files found in repository");
+        info!("⚠️  No markdown files found in repository");
         return Ok(0);
     }
 
-    println!("📄 Found {} file(s) to scan", files.len());
+    info!("📄 Found {} file(s) to scan", files.len());
 
-    // Extract all links from markdown files
-    let mut all_links = Vec::new();
+    // Extract all links from the files, remembering where each one was found
+    // Notebooks are JSON, source files only have links in comments,
+    // everything else is treated as markdown
+    let mut links = checker::LinkCollection::new();
     for (filename, content) in &files {
-        let links = checker::extract_markdown_links(content);
-        println!("   {} links found in {}", links.len(), filename);
-        all_links.extend(links);
+        let found: Vec<(String, checker::SourceLocation)> = if github::is_notebook(filename) {
+            let base_url = github::blob_url(repo_url, filename)?;
+            match checker::extract_notebook_links(content, filename, &base_url) {
+                Ok(found) => found,
+                Err(e) => {
+                    warn!("Skipping {}: {}", filename, e);
+                    continue;
+                }
+            }
+        } else if checker::is_source_file(filename) {
+            checker::extract_comment_links(content, filename)
+                .into_iter()
+                .map(|(url, position)| (url, checker::SourceLocation::at(filename, position)))
+                .collect()
+        } else {
+            checker::extract_markdown_links_with_positions(content)
+                .into_iter()
+                .map(|(url, position)| (url, checker::SourceLocation::at(filename, position)))
+                .collect()
+        };
+
+        info!("   {} links found in {}", found.len(), filename);
+        for (url, location) in found {
+            links.add(url, location);
+        }
     }
 
-    if all_links.is_empty() {
-        println!("✅ No links found to check");
+    if links.is_empty() {
+        info!("✅ No links found to check");
         return Ok(0);
     }
 
-    println!("\n🌐 Checking {} unique link(s) with concurrency {}...\n", all_links.len(), concurrency);
-
-    // Check all links for broken status
-    let results = checker::check_links(all_links, concurrency).await;
-
-    // Print results and determine exit code
-    print_results(&results, json)?;
-
-    // Count how many links are broken
-    let broken_count = results.iter()
-        .filter(|r| !r.is_ok())
-        .count();
-
-    if broken_count > 0 {
-        Ok(1)  // Exit code 1 = broken links found
-    } else {
-        Ok(0)  // Exit code 0 = all good
-    }
+    check_and_report(&links, output, check, &options, concurrency).await
 }
 
 // Handles the 'site' subcommand
 // Parameters:
 //   website_url: Website URL to crawl (e.g., "https://example.com")
-//   json: whether to output JSON format
+//   output: output format and destination (see cli::OutputArgs)
+//   check: accepted status codes and failure policy (see cli::CheckArgs)
 //   max_depth: how many levels deep to crawl (default: 1)
 //   concurrency: number of concurrent link checks
-async fn handle_site_scan(website_url: &str, json: bool, max_depth: usize, concurrency: usize) -> Result<i32> {
-    println!("🔍 Scanning website: {}", website_url);
-    println!("📊 Max crawl depth: {}", max_depth);
-
-    // Crawl the website and collect all pages
-    let pages = crawl::crawl_website(website_url, max_depth).await?;
-
-    println!("📄 Crawled {} page(s)", pages.len());
+async fn handle_site_scan(
+    website_url: &str,
+    output: &OutputArgs,
+    check: &CheckArgs,
+    max_depth: usize,
+    concurrency: usize,
+) -> Result<i32> {
+    info!("🔍 Scanning website: {}", website_url);
+    info!("📊 Max crawl depth: {}", max_depth);
+    let options = check.options()?;
+
+    // Crawl the website and collect all pages (and their stylesheets)
+    // With --cache, unchanged pages are answered with 304 Not Modified
+    let progress = Progress::crawl(Progress::enabled(output.format().is_structured()));
+    let crawled = crawl::crawl_website(website_url, max_depth, &progress, &options).await?;
+    progress.finish();
+    let pages = crawled.pages;
+
+    info!("📄 Crawled {} page(s)", pages.len());
 
     // Extract all links from all pages
-    let mut all_links = Vec::new();
+    // LinkCollection removes duplicates and remembers which pages link where
+    let mut links = checker::LinkCollection::new();
     for (page_url, html) in &pages {
-        let links = checker::extract_html_links(html, page_url);
-        println!("   {} links found on {}", links.len(), page_url);
-        all_links.extend(links);
+        let found = checker::extract_html_links(html, page_url);
+        info!("   {} links found on {}", found.len(), page_url);
+        for url in found {
+            links.add(url, checker::SourceLocation::new(page_url));
+        }
     }
 
-    if all_links.is_empty() {
-        println!("✅ No links found to check");
+    // Fonts, images and @imports referenced from same-site stylesheets
+    for (sheet_url, css) in &crawled.stylesheets {
+        let found = checker::extract_css_links(css, sheet_url);
+        info!("   {} links found in {}", found.len(), sheet_url);
+        for url in found {
+            links.add(url, checker::SourceLocation::new(sheet_url));
+        }
+    }
+
+    if links.is_empty() {
+        info!("✅ No links found to check");
+        save_cache(&options)?;  // Keep the page copies from the crawl
         return Ok(0);
     }
 
-    // Remove duplicates by converting to a HashSet and back
-    let unique_links: std::collections::HashSet<_> = all_links.into_iter().collect();
-    let all_links: Vec<_> = unique_links.into_iter().collect();
+    check_and_report(&links, output, check, &options, concurrency).await
+}
+
+// Handles the 'urls' subcommand
+// Parameters:
+//   file: file with one URL per line, or "-" for stdin
+//   output: output format and destination (see cli::OutputArgs)
+//   check: accepted status codes and failure policy (see cli::CheckArgs)
+//   concurrency: number of concurrent link checks
+async fn handle_url_list(
+    file: &std::path::Path,
+    output: &OutputArgs,
+    check: &CheckArgs,
+    concurrency: usize,
+) -> Result<i32> {
+    let options = check.options()?;
+
+    // "-" is the usual command-line spelling for stdin
+    let (source, text) = if file.as_os_str() == "-" {
+        info!("🔍 Reading URLs from stdin");
+        ("<stdin>".to_string(), std::io::read_to_string(std::io::stdin())?)
+    } else {
+        info!("🔍 Reading URLs from {}", file.display());
+        let text = std::fs::read_to_string(file)
+            .with_context(|| format!("Could not read URL list {}", file.display()))?;
+        (file.display().to_string(), text)
+    };
+
+    // Each line becomes a location, so duplicates show every line they're on
+    let mut links = checker::LinkCollection::new();
+    for (url, position) in checker::extract_list_links(&text) {
+        links.add(url, checker::SourceLocation::at(&source, position));
+    }
 
-    println!("\n🌐 Checking {} unique link(s) with concurrency {}...\n", all_links.len(), concurrency);
+    if links.is_empty() {
+        info!("✅ No URLs found to check");
+        return Ok(0);
+    }
 
-    // Check all links for broken status
-    let results = checker::check_links(all_links, concurrency).await;
+    check_and_report(&links, output, check, &options, concurrency).await
+}
 
-    // Print results and determine exit code
-    print_results(&results, json)?;
+// Handles the 'explain' subcommand
+//
+// The trace goes to stdout; the exit code follows --fail-on like a scan.
+async fn handle_explain(url: &str, check: &ExplainArgs) -> Result<i32> {
+    let explanation = checker::explain(url, &check.options()).await;
+    print!("{}", explanation.text);
+
+    match check.policy.failure_policy().outcome(&explanation.result.status) {
+        checker::Outcome::Fail => Ok(1),
+        _ => Ok(0),
+    }
+}
 
-    // Count broken links
-    let broken_count = results.iter()
-        .filter(|r| !r.is_ok())
+// Checks every collected link, reports the results and picks the exit code
+//
+// Shared by all scan subcommands.
+//
+// Returns:
+//   Ok(0) = no broken links
+//   Ok(1) = broken links found
+async fn check_and_report(
+    links: &checker::LinkCollection,
+    output: &OutputArgs,
+    check: &CheckArgs,
+    options: &checker::CheckOptions,
+    concurrency: usize,
+) -> Result<i32> {
+    info!("\n🌐 Checking {} unique link(s) with concurrency {}...\n", links.len(), concurrency);
+
+    let format = output.format();
+    let output_file = output.output_file.as_deref();
+
+    // NDJSON is streamed: each result is written as soon as its check
+    // completes, so consumers can process results during long scans
+    let mut stream = match format {
+        OutputFormat::Ndjson => Some(report::NdjsonWriter::create(output_file)?),
+        _ => None,
+    };
+
+    // --fail-on / --warn-on decide the exit code and how reports show each
+    // result; failures already in the --baseline are only warnings
+    let mut policy = check.failure_policy();
+    let baseline = match &check.baseline {
+        Some(path) => Baseline::load(path)?,
+        None => None,
+    };
+    if let Some(baseline) = &baseline {
+        policy.known_failures = baseline.urls();
+    }
+    let outcome_of = |result: &checker::LinkCheckResult| policy.outcome_of(result);
+
+    let progress = Progress::checks(Progress::enabled(format.is_structured()), links.len());
+    let mut results = checker::check_links_with(links.urls(), concurrency, options, |result| {
+        progress.link_checked(outcome_of(result));
+        if let Some(stream) = stream.as_mut() {
+            let mut result = result.clone();
+            links.attach_locations_to(&mut result);
+            stream.write(&result);
+        }
+    })
+    .await;
+    progress.finish();
+    links.attach_locations(&mut results);
+
+    // Remember fresh results for the next run
+    save_cache(options)?;
+
+    // Print results (already done for a stream)
+    match stream {
+        Some(stream) => stream.finish()?,
+        None => report::print_results(&results, output, &policy)?,
+    }
+
+    // Count warnings according to --warn-on (known failures are logged below)
+    let warned = results
+        .iter()
+        .filter(|r| policy.outcome(&r.status) == checker::Outcome::Warn)
         .count();
+    if warned > 0 {
+        warn!("{} link(s) have a status listed in --warn-on", warned);
+    }
 
-    if broken_count > 0 {
+    // With --baseline, only failures that aren't known yet count
+    let failed = match &check.baseline {
+        Some(path) => {
+            // Every failure, known or not, to compare with the baseline
+            let failures: Vec<&checker::LinkCheckResult> = results
+                .iter()
+                .filter(|r| policy.outcome(&r.status) == checker::Outcome::Fail)
+                .collect();
+            compare_with_baseline(&failures, baseline, path, check.update_baseline)?
+        }
+        None => results.iter().filter(|r| outcome_of(r) == checker::Outcome::Fail).count(),
+    };
+
+    if failed > 0 {
         Ok(1)  // Exit code 1 = broken links found
     } else {
         Ok(0)  // Exit code 0 = all good
     }
 }
 
-// Prints the results either as a table or JSON
-// Parameters:
-//   results: slice of LinkCheckResult structs
-//   json: whether to output JSON format
-fn print_results(results: &[checker::LinkCheckResult], json: bool) -> Result<()> {
-    if json {
-        // Serialize results to JSON and print
-        let json_output = serde_json::to_string_pretty(results)?;
-        println!("{}", json_output);
-    } else {
-        // Print human-readable table
-        print_table(results);
+// Compares failures with the --baseline file, returns how many are new
+//
+// Without a baseline file yet (`baseline` is None), the current failures are
+// recorded and none of them count as new. Fixed links are logged as warnings,
+// so they show up even with --quiet.
+fn compare_with_baseline(
+    failures: &[&checker::LinkCheckResult],
+    baseline: Option<Baseline>,
+    path: &std::path::Path,
+    update: bool,
+) -> Result<usize> {
+    let mut baseline = match baseline {
+        Some(baseline) => baseline,
+        None => {
+            Baseline::from_failures(failures).save(path)?;
+            info!("📌 Recorded {} failing link(s) in baseline {}", failures.len(), path.display());
+            return Ok(0);
+        }
+    };
+
+    let comparison = baseline.compare(failures);
+    if comparison.known > 0 {
+        info!("📌 {} known failure(s) from the baseline reported as warnings", comparison.known);
+    }
+    for result in &comparison.new {
+        warn!("New failure (not in baseline): {} ({})", result.url, result.status.name());
     }
-    Ok(())
-}
 
-// Prints results as a human-readable table in the terminal
-fn print_table(results: &[checker::LinkCheckResult]) {
-    // Print table header
-    println!("{:<60} {:<15} {:<30}", "URL", "STATUS", "MESSAGE");
-    println!("{}", "=".repeat(105));
+    if !comparison.fixed.is_empty() {
+        warn!("🎉 {} link(s) in the baseline no longer fail:", comparison.fixed.len());
+        for url in &comparison.fixed {
+            warn!("   {}", url);
+        }
+        if update {
+            baseline.remove(&comparison.fixed);
+            baseline.save(path)?;
+            info!("📌 Baseline {} now has {} failure(s)", path.display(), baseline.len());
+        } else {
+            warn!("   Run with --update-baseline to remove them from {}", path.display());
+        }
+    }
 
-    // Print each result
-    for result in results {
-        let status_display = format_status(&result.status);
-        let message = result.message.as_deref().unwrap_or("");
+    Ok(comparison.new.len())
+}
 
-        // Truncate URL if too long for display
-        let url_display = if result.url.len() > 57 {
-            format!("{}...", &result.url[..57])
-        } else {
-            result.url.clone()
-        };
+// Handles the 'report' subcommand
+//
+// Nothing is checked again, so the exit code is 0 unless a report can't be
+// read or written.
+fn handle_report(paths: &[std::path::PathBuf], output: &OutputArgs) -> Result<i32> {
+    let mut reports = Vec::new();
+    for path in paths {
+        let results = report::load_json(path)?;
+        info!("📄 {} link(s) in {}", results.len(), path.display());
+        reports.push(results);
+    }
 
-        println!("{:<60} {:<15} {:<30}", url_display, status_display, message);
+    let results = report::merge_reports(reports);
+    if paths.len() > 1 {
+        info!("🔗 {} unique link(s) after merging {} reports", results.len(), paths.len());
     }
 
-    println!();
+    // Saved reports are shown with the default policy (all but ok/redirect fail)
+    report::print_results(&results, output, &checker::FailurePolicy::default())?;
+    Ok(0)
+}
 
-    // Print summary
-    let ok_count = results.iter().filter(|r| r.is_ok()).count();
-    let broken_count = results.len() - ok_count;
+// Handles the 'diff' subcommand
+//
+// Returns:
+//   Ok(0) = nothing broke since the old report
+//   Ok(1) = links broke (regressions)
+fn handle_diff(
+    before: &std::path::Path,
+    after: &std::path::Path,
+    json: bool,
+    output_file: Option<&std::path::Path>,
+) -> Result<i32> {
+    let old = report::load_json(before)?;
+    let new = report::load_json(after)?;
+    info!("🔍 Comparing {} ({} links) with {} ({} links)", before.display(), old.len(), after.display(), new.len());
+
+    let diff = report::ReportDiff::between(&old, &new);
+    let rendered = if json { diff.render_json()? } else { diff.render_table()? };
+    report::write_output(&rendered, output_file)?;
+
+    Ok(if diff.has_regressions() { 1 } else { 0 })
+}
 
-    println!("📊 Summary:");
-    println!("   ✅ OK: {}", ok_count);
-    println!("   ❌ Broken: {}", broken_count);
-    println!("   📋 Total: {}", results.len());
+// Writes the result cache back to disk, if --cache is on
+fn save_cache(options: &checker::CheckOptions) -> Result<()> {
+    match &options.cache {
+        Some(cache) => cache.lock().unwrap().save(),
+        None => Ok(()),
+    }
 }
 
-// Formats the status enum as a colored string
-// (We'll add actual colors in future iterations)
-fn format_status(status: &checker::LinkStatus) -> String {
-    match status {
-        checker::LinkStatus::Ok => "✅ OK".to_string(),
-        checker::LinkStatus::Redirect(_) => "🔀 REDIRECT".to_string(),
-        checker::LinkStatus::Broken => "❌ BROKEN".to_string(),
-        checker::LinkStatus::Timeout => "⏱️  TIMEOUT".to_string(),
-        checker::LinkStatus::SslError => "🔒 SSL ERROR".to_string(),
-        checker::LinkStatus::TooManyRedirects => "🔁 TOO MANY REDIRECTS".to_string(),
-        checker::LinkStatus::DnsError => "🌐 DNS ERROR".to_string(),
-        checker::LinkStatus::Error => "⚠️  ERROR".to_string(),
+// Handles the 'cache' subcommand
+//
+// `show` prints to stdout like a report; `prune` and `clear` only log
+// what they did.
+fn handle_cache(action: CacheCommand) -> Result<i32> {
+    match action {
+        CacheCommand::Show { cache } => {
+            let cache = cache.open()?;
+            let fresh = cache.entries().filter(|(_, _, _, fresh)| *fresh).count();
+            println!("Cache: {}", cache.path().display());
+            println!("{} result(s), {} fresh, {} expired", cache.len(), fresh, cache.len() - fresh);
+            println!("{} crawled page(s) kept for conditional requests", cache.page_count());
+
+            if !cache.is_empty() {
+                println!();
+                println!("{:<20} {:<8} {:<8} URL", "STATUS", "AGE", "FRESH");
+                for (url, entry, age, fresh) in cache.entries() {
+                    let fresh = if fresh { "yes" } else { "no" };
+                    println!("{:<20} {:<8} {:<8} {}", entry.result.status.name(), cache::format_age(age), fresh, url);
+                }
+            }
+        }
+        CacheCommand::Prune { cache } => {
+            let mut cache = cache.open()?;
+            let removed = cache.prune();
+            cache.save()?;
+            info!("🧹 Removed {} expired result(s), {} left in {}", removed, cache.len(), cache.path().display());
+        }
+        CacheCommand::Clear { cache } => {
+            let mut cache = cache.open()?;
+            let removed = cache.clear();
+            cache.save()?;
+            info!("🧹 Removed {} cached result(s) from {}", removed, cache.path().display());
+        }
     }
+    Ok(0)
 }
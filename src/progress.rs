@@ -0,0 +1,180 @@
+// src/progress.rs
+// =============================================================================
+// This module draws live progress bars on stderr.
+//
+// A large scan can take minutes. Instead of staying silent we show:
+// - While crawling: pages fetched and pages still queued
+// - While checking: links checked / total, broken so far and the rate
+//
+//   ⠙ [00:01:12] ████████████░░░░░░░░ 2731/5000 links · 14 broken · 38/s
+//
+// Bars are only drawn when stderr is a terminal (never in CI logs or when
+// redirected), never for structured formats like JSON or SARIF, and not
+// with --quiet. When disabled, every method simply does nothing, so callers
+// don't need to check.
+//
+// Log lines are printed "above" the bar: logging.rs writes through
+// suspend(), which hides the bar while a line is written.
+//
+// Rust concepts:
+// - Option<T> as "maybe enabled": None means no bar
+// - Atomics: Counting broken links from a shared reference
+// - Mutex in a static: Remembering the active bar for the logger
+// =============================================================================
+
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::checker::Outcome;
+
+// The bar currently on screen, if any (used by suspend())
+static ACTIVE: Mutex<Option<ProgressBar>> = Mutex::new(None);
+
+// A progress bar that may or may not be shown
+pub struct Progress {
+    bar: Option<ProgressBar>,
+    broken: AtomicUsize,
+}
+
+impl Progress {
+    // Returns true if progress bars should be drawn
+    //
+    // Parameters:
+    //   structured: the report is a machine-readable format (not the table)
+    pub fn enabled(structured: bool) -> bool {
+        // --quiet turns off info-level messages, and progress with them
+        !structured
+            && std::io::stderr().is_terminal()
+            && tracing::enabled!(tracing::Level::INFO)
+    }
+
+    // Progress for crawling a website (the total grows as pages are found)
+    pub fn crawl(enabled: bool) -> Self {
+        Self::start(
+            enabled,
+            1,
+            "{spinner} [{elapsed_precise}] {bar:30} {pos} page(s) fetched · {msg}",
+            "1 queued",
+        )
+    }
+
+    // Progress for checking `total` links
+    pub fn checks(enabled: bool, total: usize) -> Self {
+        Self::start(
+            enabled,
+            total,
+            "{spinner} [{elapsed_precise}] {bar:30} {pos}/{len} links · {msg} · {per_sec}",
+            "0 broken",
+        )
+    }
+
+    fn start(enabled: bool, total: usize, template: &str, message: &str) -> Self {
+        let bar = enabled.then(|| {
+            let bar = ProgressBar::new(total as u64);
+            // The template is a constant, so a parse error is a bug
+            bar.set_style(
+                ProgressStyle::with_template(template)
+                    .expect("invalid progress template")
+                    .progress_chars("█░ "),
+            );
+            bar.enable_steady_tick(Duration::from_millis(100));
+            *ACTIVE.lock().unwrap() = Some(bar.clone());
+            bar
+        });
+
+        let progress = Progress {
+            bar,
+            broken: AtomicUsize::new(0),
+        };
+        progress.set_message(message);
+        progress
+    }
+
+    // Crawl update: `fetched` pages done, `queued` still waiting
+    pub fn page_fetched(&self, fetched: usize, queued: usize) {
+        if let Some(bar) = &self.bar {
+            bar.set_length((fetched + queued) as u64);
+            bar.set_position(fetched as u64);
+            bar.set_message(format!("{} queued", queued));
+        }
+    }
+
+    // Check update: one more link is done
+    //
+    // Only results that fail the run (see --fail-on) count as broken.
+    pub fn link_checked(&self, outcome: Outcome) {
+        if outcome == Outcome::Fail {
+            let broken = self.broken.fetch_add(1, Ordering::Relaxed) + 1;
+            self.set_message(&format!("{} broken", broken));
+        }
+        if let Some(bar) = &self.bar {
+            bar.inc(1);
+        }
+    }
+
+    fn set_message(&self, message: &str) {
+        if let Some(bar) = &self.bar {
+            bar.set_message(message.to_string());
+        }
+    }
+
+    // Removes the bar from the screen
+    pub fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+            *ACTIVE.lock().unwrap() = None;
+        }
+    }
+}
+
+// Make sure the bar disappears even if we return early with an error
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+// Runs `f` with the active progress bar (if any) hidden
+//
+// Used by the logger so log lines don't get mixed into the bar.
+pub fn suspend<R>(f: impl FnOnce() -> R) -> R {
+    let active = ACTIVE.lock().unwrap().clone();
+    match active {
+        Some(bar) => bar.suspend(f),
+        None => f(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. What does enabled.then(|| ...) do?
+//    - bool::then runs the closure only if the bool is true
+//    - It returns Some(result) or None - a neat way to build an Option
+//
+// 2. Why is ProgressBar cloned into ACTIVE?
+//    - A ProgressBar is a handle (like Client): clones share the same bar
+//    - The logger needs to reach it without being passed a reference
+//
+// 3. Why impl Drop?
+//    - Drop runs when a value goes out of scope
+//    - If a scan fails halfway, the bar is still cleared from the terminal
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hidden_progress_counts_broken() {
+        let progress = Progress::checks(false, 3);
+        progress.link_checked(Outcome::Fail);
+        progress.link_checked(Outcome::Warn);
+        progress.link_checked(Outcome::Pass);
+        assert_eq!(progress.broken.load(Ordering::Relaxed), 1);
+        assert!(progress.bar.is_none());
+    }
+}
@@ -0,0 +1,120 @@
+// src/report/csv.rs
+// =============================================================================
+// This module renders results as CSV (comma-separated values).
+//
+// CSV opens in any spreadsheet and is easy to process with scripts.
+// There is one row per link per place it was found, so a link used in three
+// files produces three rows:
+//
+//   url,status,http_code,redirect_target,message,source,cell,line,column
+//   https://example.com/gone,broken,404,,HTTP 404,README.md,,12,5
+//
+// Values are quoted following RFC 4180: fields containing a comma, quote or
+// newline are wrapped in double quotes, and quotes inside are doubled.
+//
+// Rust concepts:
+// - Option::map + unwrap_or_default: Turning missing values into empty cells
+// - Arrays of chars as patterns: Checking for several characters at once
+// =============================================================================
+
+use crate::checker::{LinkCheckResult, LinkStatus, SourceLocation};
+
+const HEADER: &str = "url,status,http_code,redirect_target,message,source,cell,line,column";
+
+// Renders results as CSV with a header row
+pub fn render(results: &[LinkCheckResult]) -> String {
+    let mut out = String::new();
+    out.push_str(HEADER);
+    out.push('\n');
+
+    for result in results {
+        let locations: Vec<Option<&SourceLocation>> = if result.locations.is_empty() {
+            vec![None]
+        } else {
+            result.locations.iter().map(Some).collect()
+        };
+
+        let redirect_target = match &result.status {
+            LinkStatus::Redirect { target, .. } => target.as_str(),
+            _ => "",
+        };
+
+        for location in locations {
+            let number = |value: Option<usize>| value.map(|n| n.to_string()).unwrap_or_default();
+            let fields = [
+                escape_field(&result.url),
+                result.status.name().to_string(),
+                result.status.code().map(|c| c.to_string()).unwrap_or_default(),
+                escape_field(redirect_target),
+                escape_field(result.message.as_deref().unwrap_or("")),
+                escape_field(location.map(|l| l.source.as_str()).unwrap_or("")),
+                number(location.and_then(|l| l.cell)),
+                number(location.and_then(|l| l.line)),
+                number(location.and_then(|l| l.column)),
+            ];
+            out.push_str(&fields.join(","));
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+// Quotes a field if it contains characters that have a meaning in CSV
+//
+// Example: say "hi", ok -> "say ""hi"", ok"
+fn escape_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why one row per location instead of one per link?
+//    - Spreadsheets can't hold a list in a cell nicely
+//    - One row per place makes it easy to filter by file
+//
+// 2. What does text.contains([',', '"', '\n', '\r']) do?
+//    - An array of chars works as a pattern: true if ANY of them appears
+//
+// 3. Why is number a closure?
+//    - It's a tiny helper only used here, so it lives next to its callers
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_per_location() {
+        let result = LinkCheckResult {
+            url: "https://example.com/gone".to_string(),
+            status: LinkStatus::Broken { code: 404 },
+            message: Some("HTTP 404".to_string()),
+            locations: vec![
+                SourceLocation {
+                    line: Some(12),
+                    column: Some(5),
+                    ..SourceLocation::new("README.md")
+                },
+                SourceLocation::new("docs/guide.md"),
+            ],
+        };
+        let csv = render(&[result]);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], HEADER);
+        assert_eq!(lines[1], "https://example.com/gone,broken,404,,HTTP 404,README.md,,12,5");
+        assert_eq!(lines[2], "https://example.com/gone,broken,404,,HTTP 404,docs/guide.md,,,");
+    }
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("plain"), "plain");
+        assert_eq!(escape_field("say \"hi\", ok"), "\"say \"\"hi\"\", ok\"");
+    }
+}
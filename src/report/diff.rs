@@ -0,0 +1,212 @@
+// src/report/diff.rs
+// =============================================================================
+// This module compares two saved JSON reports (`link-guardian diff`).
+//
+// Given last week's report and today's, it lists what changed:
+// - Newly broken: failing now, but OK before (or not linked before)
+// - Newly fixed: OK now, but failing before
+// - Changed status: a different status or code, e.g. broken -> server_error
+// - Changed redirect target: still a redirect, but to another URL
+//
+// Links are matched by URL. Links that only appear in the old report were
+// removed from the pages and aren't listed.
+//
+//   link-guardian diff last-week.json today.json
+//   link-guardian diff last-week.json today.json --json
+//
+// Rust concepts:
+// - Lifetimes: A Change borrows the results it compares instead of copying
+// - #[derive(Serialize)] on borrowed data: JSON output without cloning
+// =============================================================================
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use crate::checker::{LinkCheckResult, LinkStatus};
+
+// One link whose result changed
+#[derive(Debug, Serialize)]
+pub struct Change<'a> {
+    pub url: &'a str,
+    /// The result in the old report, None if the link is new
+    pub before: Option<&'a LinkCheckResult>,
+    pub after: &'a LinkCheckResult,
+}
+
+// Everything that changed between two reports
+#[derive(Debug, Default, Serialize)]
+pub struct ReportDiff<'a> {
+    pub newly_broken: Vec<Change<'a>>,
+    pub newly_fixed: Vec<Change<'a>>,
+    pub changed_status: Vec<Change<'a>>,
+    pub changed_redirect: Vec<Change<'a>>,
+}
+
+impl<'a> ReportDiff<'a> {
+    // Compares the old report with the new one
+    //
+    // The lists follow the order of the new report.
+    pub fn between(before: &'a [LinkCheckResult], after: &'a [LinkCheckResult]) -> Self {
+        let old: HashMap<&str, &LinkCheckResult> = before.iter().map(|r| (r.url.as_str(), r)).collect();
+        let mut diff = ReportDiff::default();
+
+        for result in after {
+            let previous = old.get(result.url.as_str()).copied();
+            let change = Change {
+                url: &result.url,
+                before: previous,
+                after: result,
+            };
+
+            let previous = match previous {
+                Some(previous) => previous,
+                // A new link only matters if it is broken
+                None if !result.is_ok() => {
+                    diff.newly_broken.push(change);
+                    continue;
+                }
+                None => continue,
+            };
+
+            match (previous.is_ok(), result.is_ok()) {
+                (true, false) => diff.newly_broken.push(change),
+                (false, true) => diff.newly_fixed.push(change),
+                _ => match (&previous.status, &result.status) {
+                    (LinkStatus::Redirect { code: a, target: old }, LinkStatus::Redirect { code: b, target: new })
+                        if a == b && old != new =>
+                    {
+                        diff.changed_redirect.push(change)
+                    }
+                    (old, new) if old != new => diff.changed_status.push(change),
+                    _ => {}
+                },
+            }
+        }
+
+        diff
+    }
+
+    // True if any link broke since the old report
+    pub fn has_regressions(&self) -> bool {
+        !self.newly_broken.is_empty()
+    }
+
+    // Renders the changes as sections of plain text
+    pub fn render_table(&self) -> Result<String, fmt::Error> {
+        let mut out = String::new();
+        let sections = [
+            ("🔴 Newly broken", &self.newly_broken),
+            ("🟢 Newly fixed", &self.newly_fixed),
+            ("🔄 Changed status", &self.changed_status),
+            ("🔀 Changed redirect target", &self.changed_redirect),
+        ];
+
+        for (title, changes) in sections {
+            if changes.is_empty() {
+                continue;
+            }
+            writeln!(out, "{} ({})", title, changes.len())?;
+            for change in changes.iter() {
+                let before = change.before.map_or("(new link)".to_string(), describe);
+                writeln!(out, "   {}", change.url)?;
+                writeln!(out, "      {} -> {}", before, describe(change.after))?;
+            }
+            writeln!(out)?;
+        }
+
+        writeln!(out, "📊 Diff summary:")?;
+        writeln!(out, "   🔴 Newly broken: {}", self.newly_broken.len())?;
+        writeln!(out, "   🟢 Newly fixed: {}", self.newly_fixed.len())?;
+        writeln!(out, "   🔄 Changed status: {}", self.changed_status.len())?;
+        writeln!(out, "   🔀 Changed redirect target: {}", self.changed_redirect.len())?;
+        Ok(out)
+    }
+
+    // Renders the changes as JSON, one array per kind of change
+    pub fn render_json(&self) -> serde_json::Result<String> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        Ok(json)
+    }
+}
+
+// A short description of a result, e.g. "broken (404)" or
+// "redirect (301) to https://example.com/new"
+fn describe(result: &LinkCheckResult) -> String {
+    let name = result.status.name();
+    match (&result.status, result.status.code()) {
+        (LinkStatus::Redirect { code, target }, _) => format!("{} ({}) to {}", name, code, target),
+        (_, Some(code)) => format!("{} ({})", name, code),
+        (_, None) => name.to_string(),
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why does ReportDiff have a lifetime 'a?
+//    - Its Change entries point into the two result lists
+//    - The diff can't outlive them, and nothing is copied
+//
+// 2. What does `match (a, b)` on a tuple do?
+//    - It matches both values at once: (true, false) means "OK before,
+//      failing now"
+//    - `_` catches every other combination
+//
+// 3. Why is a link missing from the old report "newly broken"?
+//    - A page that starts linking to a dead URL is a regression too
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redirect(target: &str) -> LinkStatus {
+        LinkStatus::Redirect {
+            code: 301,
+            target: target.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_categories() {
+        let before = vec![
+            LinkCheckResult::new("https://a.com", LinkStatus::Ok, None, Vec::new()),
+            LinkCheckResult::new("https://b.com", LinkStatus::Broken { code: 404 }, None, Vec::new()),
+            LinkCheckResult::new("https://c.com", LinkStatus::Broken { code: 404 }, None, Vec::new()),
+            LinkCheckResult::new("https://d.com", redirect("https://d.com/old"), None, Vec::new()),
+            LinkCheckResult::new("https://gone.com", LinkStatus::Ok, None, Vec::new()),
+        ];
+        let after = vec![
+            LinkCheckResult::new("https://a.com", LinkStatus::Timeout, None, Vec::new()),
+            LinkCheckResult::new("https://b.com", LinkStatus::Ok, None, Vec::new()),
+            LinkCheckResult::new("https://c.com", LinkStatus::Broken { code: 410 }, None, Vec::new()),
+            LinkCheckResult::new("https://d.com", redirect("https://d.com/new"), None, Vec::new()),
+            LinkCheckResult::new("https://new.com", LinkStatus::DnsError, None, Vec::new()),
+        ];
+        let diff = ReportDiff::between(&before, &after);
+
+        let urls = |changes: &[Change]| changes.iter().map(|c| c.url.to_string()).collect::<Vec<_>>();
+        assert_eq!(urls(&diff.newly_broken), ["https://a.com", "https://new.com"]);
+        assert_eq!(urls(&diff.newly_fixed), ["https://b.com"]);
+        assert_eq!(urls(&diff.changed_status), ["https://c.com"]);
+        assert_eq!(urls(&diff.changed_redirect), ["https://d.com"]);
+        assert!(diff.has_regressions());
+
+        let table = diff.render_table().unwrap();
+        assert!(table.contains("broken (404) -> broken (410)"));
+        assert!(table.contains("(new link) -> dns_error"));
+    }
+
+    #[test]
+    fn test_diff_json() {
+        let before = vec![LinkCheckResult::new("https://a.com", LinkStatus::Ok, None, Vec::new())];
+        let after = vec![LinkCheckResult::new("https://a.com", LinkStatus::Ok, None, Vec::new())];
+        let diff = ReportDiff::between(&before, &after);
+        assert!(!diff.has_regressions());
+
+        let json: serde_json::Value = serde_json::from_str(&diff.render_json().unwrap()).unwrap();
+        assert_eq!(json["newly_broken"].as_array().unwrap().len(), 0);
+    }
+}
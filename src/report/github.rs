@@ -0,0 +1,164 @@
+// src/report/github.rs
+// =============================================================================
+// This module renders results as GitHub Actions workflow commands.
+//
+// When a line like this is printed inside a GitHub Actions job:
+//   ::error file=README.md,line=12,col=5,title=Broken link::https://... (HTTP 404)
+// GitHub turns it into an annotation shown inline on the pull request diff.
+// https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions
+//
+// We print:
+// - ::error for links that fail the run (see --fail-on)
+// - ::warning for statuses listed in --warn-on, and for redirects (they
+//   work, but should probably be updated)
+// followed by the usual table so the job log stays readable.
+//
+// Rust concepts:
+// - match + continue: Skipping results that need no annotation
+// - String escaping: Percent-encoding the characters GitHub treats specially
+// =============================================================================
+
+use std::fmt::{self, Write};
+
+use super::table;
+use crate::checker::{FailurePolicy, LinkCheckResult, LinkStatus, Outcome, SourceLocation};
+
+// Renders one annotation per failing, warned or redirected link occurrence
+pub fn render(results: &[LinkCheckResult], policy: &FailurePolicy) -> Result<String, fmt::Error> {
+    let mut out = String::new();
+
+    for result in results {
+        let redirected = matches!(result.status, LinkStatus::Redirect { .. });
+        let (command, title) = match policy.outcome_of(result) {
+            Outcome::Fail => ("error", "Broken link"),
+            Outcome::Warn | Outcome::Pass if redirected => ("warning", "Redirected link"),
+            Outcome::Warn => ("warning", "Link warning"),
+            Outcome::Pass => continue,
+        };
+
+        let mut message = format!("{} is {}", result.url, result.status.name().replace('_', " "));
+        if let Some(detail) = &result.message {
+            message.push_str(&format!(" ({})", detail));
+        }
+
+        if result.locations.is_empty() {
+            writeln!(out, "::{} title={}::{}", command, title, escape_data(&message))?;
+            continue;
+        }
+
+        for location in &result.locations {
+            let mut properties = Vec::new();
+
+            // Pages from a site crawl aren't files in the repository, so
+            // the annotation can't point at them - it still shows in the summary
+            if is_repository_file(location) {
+                properties.push(format!("file={}", escape_property(&location.source)));
+
+                // Notebook line numbers are relative to a cell, not the file
+                if location.cell.is_none() {
+                    if let Some(line) = location.line {
+                        properties.push(format!("line={}", line));
+                    }
+                    if let Some(column) = location.column {
+                        properties.push(format!("col={}", column));
+                    }
+                }
+            }
+            properties.push(format!("title={}", escape_property(title)));
+
+            let mut text = message.clone();
+            if !is_repository_file(location) || location.cell.is_some() {
+                text.push_str(&format!(" at {}", location));
+            }
+
+            writeln!(out, "::{} {}::{}", command, properties.join(","), escape_data(&text))?;
+        }
+    }
+
+    // The normal table follows, so humans reading the log see everything
+    out.push_str(&table::render(results, policy)?);
+    Ok(out)
+}
+
+// Returns true if the location is a path inside the repository
+fn is_repository_file(location: &SourceLocation) -> bool {
+    !(location.source.starts_with("http://") || location.source.starts_with("https://"))
+}
+
+// Escapes the message part of a workflow command
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+// Escapes a property value (file=..., title=...) of a workflow command
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. What does `Outcome::Pass => continue` do inside a match?
+//    - continue skips to the next result of the for loop
+//    - The match has to produce a value, and `continue` never does, so the
+//      compiler accepts it in any arm
+//
+// 2. Why escape % : and , ?
+//    - GitHub uses "::" and "," to split the command into parts
+//    - Percent-encoding them keeps URLs with commas or colons intact
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://example.com/a,b";
+
+    #[test]
+    fn test_error_annotation() {
+        let location = SourceLocation {
+            line: Some(12),
+            column: Some(5),
+            ..SourceLocation::new("README.md")
+        };
+        let broken = LinkCheckResult::new(URL, LinkStatus::Broken { code: 404 }, Some("HTTP 404"), vec![location]);
+        let output = render(&[broken], &FailurePolicy::default()).unwrap();
+        assert!(output.starts_with(
+            "::error file=README.md,line=12,col=5,title=Broken link::https://example.com/a,b is broken (HTTP 404)\n"
+        ));
+    }
+
+    #[test]
+    fn test_redirect_is_warning_and_pages_have_no_file() {
+        let location = SourceLocation::new("https://example.com/");
+        let redirect = LinkStatus::Redirect {
+            code: 301,
+            target: "https://x".to_string(),
+        };
+        let redirect = LinkCheckResult::new(URL, redirect, None, vec![location]);
+        let output = render(&[redirect], &FailurePolicy::default()).unwrap();
+        assert!(output.starts_with("::warning title=Redirected link::"));
+        assert!(output.contains(" at https://example.com/\n"));
+    }
+
+    #[test]
+    fn test_follows_failure_policy() {
+        let policy = FailurePolicy {
+            fail_on: Some(vec!["broken".to_string()]),
+            warn_on: vec!["timeout".to_string()],
+            ..FailurePolicy::default()
+        };
+        let location = SourceLocation::new("README.md");
+        let timeout = LinkCheckResult::new(URL, LinkStatus::Timeout, Some("Request timed out"), vec![location.clone()]);
+        let timeout = render(&[timeout], &policy).unwrap();
+        assert!(timeout.starts_with("::warning file=README.md,title=Link warning::"));
+
+        let ssl = render(&[LinkCheckResult::new(URL, LinkStatus::SslError, None, vec![location])], &policy).unwrap();
+        assert!(!ssl.starts_with("::"));
+    }
+
+    #[test]
+    fn test_escape_property() {
+        assert_eq!(escape_property("a:b,c%"), "a%3Ab%2Cc%25");
+    }
+}
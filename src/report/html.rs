@@ -0,0 +1,262 @@
+// src/report/html.rs
+// =============================================================================
+// This module renders results as a self-contained HTML report.
+//
+// The report is a single static file (no external CSS, JS or images) so it
+// can be attached to a CI run, emailed or opened straight from disk:
+// - Summary cards with the number of links per status
+// - A table of all links that can be sorted (click a header) and filtered
+//   (by text, by status, or to the links that fail the run / are warnings
+//   according to --fail-on and --warn-on)
+// - The same links grouped by the file or page they were found in
+// - The redirect target for redirected links
+//
+// Rust concepts:
+// - writeln!: Building the page piece by piece into a String
+// - HashMap: Counting and grouping results
+// =============================================================================
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+use super::{escape_markup, group_by_source};
+use crate::checker::{FailurePolicy, LinkCheckResult, LinkStatus, Outcome};
+
+// Renders results as a complete HTML document
+pub fn render(results: &[LinkCheckResult], policy: &FailurePolicy) -> Result<String, fmt::Error> {
+    let mut out = String::new();
+
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html lang=\"en\">")?;
+    writeln!(out, "<head>")?;
+    writeln!(out, "<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>link-guardian report</title>")?;
+    writeln!(out, "<style>{}</style>", STYLE)?;
+    writeln!(out, "</head>")?;
+    writeln!(out, "<body>")?;
+    writeln!(out, "<h1>link-guardian report</h1>")?;
+
+    write_summary(&mut out, results)?;
+
+    // Filter controls apply to every table on the page
+    writeln!(out, "<div class=\"controls\">")?;
+    writeln!(out, "<input id=\"filter\" type=\"search\" placeholder=\"Filter by URL, message or source\">")?;
+    writeln!(out, "<select id=\"status\"><option value=\"\">All statuses</option><option value=\"fail\">Failing only</option><option value=\"warn\">Warnings only</option>")?;
+    for name in statuses_present(results) {
+        writeln!(out, "<option value=\"{0}\">{0}</option>", name)?;
+    }
+    writeln!(out, "</select>")?;
+    writeln!(out, "</div>")?;
+
+    writeln!(out, "<h2>All links</h2>")?;
+    let all: Vec<&LinkCheckResult> = results.iter().collect();
+    write_table(&mut out, &all, policy)?;
+
+    writeln!(out, "<h2>By source</h2>")?;
+    for (source, cases) in group_by_source(results, "(unknown source)") {
+        let group: Vec<&LinkCheckResult> = cases.iter().map(|(result, _)| *result).collect();
+        let count = |outcome| group.iter().filter(|r| policy.outcome_of(r) == outcome).count();
+        let (failing, warnings) = (count(Outcome::Fail), count(Outcome::Warn));
+        writeln!(
+            out,
+            "<details{}><summary><code>{}</code> &mdash; {} link(s), {} failing, {} warning(s)</summary>",
+            if failing > 0 { " open" } else { "" },
+            escape_markup(&source),
+            group.len(),
+            failing,
+            warnings
+        )?;
+        write_table(&mut out, &group, policy)?;
+        writeln!(out, "</details>")?;
+    }
+
+    writeln!(out, "<script>{}</script>", SCRIPT)?;
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")?;
+
+    Ok(out)
+}
+
+// Writes one card per status with the number of links in it
+fn write_summary(out: &mut String, results: &[LinkCheckResult]) -> fmt::Result {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for result in results {
+        *counts.entry(result.status.name()).or_insert(0) += 1;
+    }
+
+    writeln!(out, "<div class=\"summary\">")?;
+    writeln!(out, "<div class=\"card\"><b>{}</b>total</div>", results.len())?;
+    for name in LinkStatus::NAMES {
+        if let Some(count) = counts.get(name) {
+            writeln!(out, "<div class=\"card s-{0}\"><b>{1}</b>{0}</div>", name, count)?;
+        }
+    }
+    writeln!(out, "</div>")
+}
+
+// Writes a sortable table of results
+fn write_table(out: &mut String, results: &[&LinkCheckResult], policy: &FailurePolicy) -> fmt::Result {
+    writeln!(out, "<table class=\"links\">")?;
+    writeln!(
+        out,
+        "<thead><tr><th>Status</th><th>URL</th><th>Message</th><th>Redirects to</th><th>Found in</th></tr></thead>"
+    )?;
+    writeln!(out, "<tbody>")?;
+
+    for result in results {
+        let name = result.status.name();
+        let redirect = match &result.status {
+            LinkStatus::Redirect { target, .. } => escape_markup(target),
+            _ => String::new(),
+        };
+        let found_in: Vec<String> = result
+            .locations
+            .iter()
+            .map(|l| escape_markup(&l.to_string()))
+            .collect();
+
+        writeln!(
+            out,
+            "<tr data-status=\"{0}\" data-outcome=\"{1}\"><td><span class=\"badge s-{0}\">{0}</span></td>\
+             <td><a href=\"{2}\">{2}</a></td><td>{3}</td><td>{4}</td><td>{5}</td></tr>",
+            name,
+            match policy.outcome_of(result) {
+                Outcome::Pass => "pass",
+                Outcome::Warn => "warn",
+                Outcome::Fail => "fail",
+            },
+            escape_markup(&result.url),
+            escape_markup(result.message.as_deref().unwrap_or("")),
+            redirect,
+            found_in.join("<br>")
+        )?;
+    }
+
+    writeln!(out, "</tbody>")?;
+    writeln!(out, "</table>")
+}
+
+// Statuses that occur in the results, in summary order
+fn statuses_present(results: &[LinkCheckResult]) -> Vec<&'static str> {
+    LinkStatus::NAMES
+        .iter()
+        .copied()
+        .filter(|name| results.iter().any(|r| r.status.name() == *name))
+        .collect()
+}
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+h1 { margin-bottom: 0.5rem; }
+.summary { display: flex; flex-wrap: wrap; gap: 0.75rem; margin: 1rem 0; }
+.card { border: 1px solid #ddd; border-radius: 6px; padding: 0.5rem 1rem; min-width: 6rem; }
+.card b { display: block; font-size: 1.5rem; }
+.controls { display: flex; gap: 0.5rem; margin: 1rem 0; }
+.controls input { flex: 1; padding: 0.4rem; }
+table { border-collapse: collapse; width: 100%; margin-bottom: 1rem; font-size: 0.9rem; }
+th, td { text-align: left; padding: 0.35rem 0.5rem; border-bottom: 1px solid #eee; vertical-align: top; word-break: break-all; }
+th { cursor: pointer; background: #f6f6f6; user-select: none; }
+th.asc::after { content: " \25B2"; }
+th.desc::after { content: " \25BC"; }
+details { margin-bottom: 0.5rem; }
+summary { cursor: pointer; padding: 0.25rem 0; }
+.badge { padding: 0.1rem 0.4rem; border-radius: 4px; font-size: 0.8rem; white-space: nowrap; }
+.s-ok { background: #e6f4ea; }
+.s-redirect { background: #e8f0fe; }
+.s-unauthorized, .s-forbidden, .s-rate_limited, .s-server_error, .s-timeout, .s-connection_reset, .s-error { background: #fef7e0; }
+.s-broken, .s-client_error, .s-unexpected_status, .s-dns_error, .s-connection_refused, .s-ssl_error, .s-too_many_redirects, .s-invalid_url { background: #fce8e6; }
+"#;
+
+const SCRIPT: &str = r#"
+(function () {
+  var filter = document.getElementById('filter');
+  var status = document.getElementById('status');
+
+  function applyFilters() {
+    var text = filter.value.toLowerCase();
+    var wanted = status.value;
+    document.querySelectorAll('table.links tbody tr').forEach(function (row) {
+      var matchesText = row.textContent.toLowerCase().indexOf(text) !== -1;
+      var matchesStatus = wanted === '' ||
+        (wanted === 'fail' || wanted === 'warn' ? row.dataset.outcome === wanted : row.dataset.status === wanted);
+      row.style.display = matchesText && matchesStatus ? '' : 'none';
+    });
+  }
+  filter.addEventListener('input', applyFilters);
+  status.addEventListener('change', applyFilters);
+
+  document.querySelectorAll('table.links th').forEach(function (th) {
+    th.addEventListener('click', function () {
+      var table = th.closest('table');
+      var column = Array.prototype.indexOf.call(th.parentNode.children, th);
+      var ascending = !th.classList.contains('asc');
+      table.querySelectorAll('th').forEach(function (h) { h.classList.remove('asc', 'desc'); });
+      th.classList.add(ascending ? 'asc' : 'desc');
+      var body = table.tBodies[0];
+      Array.prototype.slice.call(body.rows)
+        .sort(function (a, b) {
+          var x = a.cells[column].textContent, y = b.cells[column].textContent;
+          return ascending ? x.localeCompare(y) : y.localeCompare(x);
+        })
+        .forEach(function (row) { body.appendChild(row); });
+    });
+  });
+})();
+"#;
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why embed CSS and JavaScript as constants?
+//    - The report has to work as a single file, even offline
+//    - r#"..."# raw strings let us paste them in without escaping quotes
+//
+// 2. Why escape_markup() on every value?
+//    - URLs and messages come from the web and may contain < or &
+//    - Without escaping, a URL could break (or inject into) the page
+//
+// 3. What is *counts.entry(...).or_insert(0) += 1?
+//    - entry() finds the slot for a key, or_insert(0) creates it if missing
+//    - The * dereferences the returned &mut usize so we can add to it
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::SourceLocation;
+
+    #[test]
+    fn test_report_contents() {
+        let results = vec![
+            LinkCheckResult {
+                url: "https://example.com/old".to_string(),
+                status: LinkStatus::Redirect {
+                    code: 301,
+                    target: "https://example.com/new".to_string(),
+                },
+                message: Some("HTTP 301 -> https://example.com/new".to_string()),
+                locations: vec![SourceLocation::new("README.md")],
+            },
+            LinkCheckResult {
+                url: "https://example.com/<script>".to_string(),
+                status: LinkStatus::Broken { code: 404 },
+                message: Some("HTTP 404".to_string()),
+                locations: vec![SourceLocation::new("docs/guide.md")],
+            },
+        ];
+        let policy = FailurePolicy {
+            fail_on: None,
+            warn_on: vec!["broken".to_string()],
+            ..FailurePolicy::default()
+        };
+        let html = render(&results, &policy).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<div class=\"card s-broken\"><b>1</b>broken</div>"));
+        assert!(html.contains("<td>https://example.com/new</td>"));
+        assert!(html.contains("<code>docs/guide.md</code> &mdash; 1 link(s), 0 failing, 1 warning(s)"));
+        assert!(html.contains("data-status=\"broken\" data-outcome=\"warn\""));
+        assert!(html.contains("https://example.com/&lt;script&gt;"));
+        assert!(!html.contains("https://example.com/<script>"));
+    }
+}
@@ -0,0 +1,182 @@
+// src/report/junit.rs
+// =============================================================================
+// This module renders results as JUnit XML.
+//
+// Jenkins, GitLab and most CI systems show JUnit reports in their test UI.
+// We map link checking onto tests like this:
+// - One <testsuite> per scanned file or crawled page
+// - One <testcase> per link found in it
+// - Links that fail the run (see --fail-on) get a <failure> with the status
+//   and message; statuses listed in --warn-on are marked <skipped>
+//
+// A link used in three files shows up in all three suites, so every file's
+// report is complete on its own.
+//
+// Rust concepts:
+// - Raw strings: Writing XML without escaping every quote
+// - Early return: Passing links get a self-closing <testcase />
+// =============================================================================
+
+use std::fmt::{self, Write};
+
+use super::{escape_markup, group_by_source};
+use crate::checker::{FailurePolicy, LinkCheckResult, Outcome, SourceLocation};
+
+// Name of the suite for links we don't know the location of
+const UNKNOWN_SOURCE: &str = "links";
+
+// Renders results as a JUnit XML report
+pub fn render(results: &[LinkCheckResult], policy: &FailurePolicy) -> Result<String, fmt::Error> {
+    let suites = group_by_source(results, UNKNOWN_SOURCE);
+    let count = |outcome: Outcome| {
+        suites
+            .iter()
+            .flat_map(|(_, cases)| cases.iter())
+            .filter(|(result, _)| policy.outcome_of(result) == outcome)
+            .count()
+    };
+
+    let total = suites.iter().map(|(_, cases)| cases.len()).sum::<usize>();
+
+    let mut out = String::new();
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        out,
+        r#"<testsuites name="link-guardian" tests="{}" failures="{}" skipped="{}">"#,
+        total,
+        count(Outcome::Fail),
+        count(Outcome::Warn)
+    )?;
+
+    for (source, cases) in &suites {
+        let suite_count = |outcome: Outcome| {
+            cases.iter().filter(|(result, _)| policy.outcome_of(result) == outcome).count()
+        };
+        writeln!(
+            out,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" skipped="{}">"#,
+            escape_markup(source),
+            cases.len(),
+            suite_count(Outcome::Fail),
+            suite_count(Outcome::Warn)
+        )?;
+
+        for (result, location) in cases {
+            write_case(&mut out, source, result, *location, policy.outcome_of(result))?;
+        }
+
+        writeln!(out, "  </testsuite>")?;
+    }
+
+    writeln!(out, "</testsuites>")?;
+    Ok(out)
+}
+
+// Writes a single <testcase>, with a <failure> if the link fails the run
+// or <skipped> if its status is only a warning
+fn write_case(
+    out: &mut String,
+    source: &str,
+    result: &LinkCheckResult,
+    location: Option<&SourceLocation>,
+    outcome: Outcome,
+) -> fmt::Result {
+    write!(
+        out,
+        r#"    <testcase name="{}" classname="{}""#,
+        escape_markup(&result.url),
+        escape_markup(source)
+    )?;
+
+    // file/line attributes let CI UIs link the failure back to the source
+    if let Some(location) = location {
+        write!(out, r#" file="{}""#, escape_markup(&location.source))?;
+        if let (Some(line), None) = (location.line, location.cell) {
+            write!(out, r#" line="{}""#, line)?;
+        }
+    }
+
+    let element = match outcome {
+        Outcome::Pass => return writeln!(out, " />"),
+        Outcome::Warn => "skipped",
+        Outcome::Fail => "failure",
+    };
+
+    let message = result.message.as_deref().unwrap_or("");
+    writeln!(out, ">")?;
+    write!(
+        out,
+        r#"      <{} type="{}" message="{}">{} is {}"#,
+        element,
+        result.status.name(),
+        escape_markup(message),
+        escape_markup(&result.url),
+        result.status.name().replace('_', " ")
+    )?;
+    if !message.is_empty() {
+        write!(out, " ({})", escape_markup(message))?;
+    }
+    if let Some(location) = location {
+        write!(out, " at {}", escape_markup(&location.to_string()))?;
+    }
+    writeln!(out, "</{}>", element)?;
+    writeln!(out, "    </testcase>")
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. What is r#"..."#?
+//    - A "raw string": backslashes and quotes inside are taken literally
+//    - Perfect for XML, which is full of double quotes
+//
+// 2. Why escape_markup()?
+//    - URLs can contain & and other characters that are special in XML
+//    - Escaping turns & into &amp; so the report stays valid XML
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::LinkStatus;
+
+    #[test]
+    fn test_suite_per_source() {
+        let readme = SourceLocation::new("README.md");
+        let both = vec![readme.clone(), SourceLocation::new("docs/intro.md")];
+        let broken = LinkStatus::Broken { code: 404 };
+        let results = vec![
+            LinkCheckResult::new("https://a.com/?x=1&y=2", LinkStatus::Ok, Some("HTTP 200"), both),
+            LinkCheckResult::new("https://b.com/missing", broken, Some("HTTP 404"), vec![readme]),
+        ];
+        let xml = render(&results, &FailurePolicy::default()).unwrap();
+
+        assert!(xml.contains(r#"<testsuites name="link-guardian" tests="3" failures="1" skipped="0">"#));
+        assert!(xml.contains(r#"<testsuite name="README.md" tests="2" failures="1" skipped="0">"#));
+        assert!(xml.contains(r#"<testsuite name="docs/intro.md" tests="1" failures="0" skipped="0">"#));
+        assert!(xml.contains("https://a.com/?x=1&amp;y=2"));
+        assert!(xml.contains(r#"<failure type="broken" message="HTTP 404">"#));
+    }
+
+    #[test]
+    fn test_links_without_location() {
+        let ok = LinkCheckResult::new("https://a.com", LinkStatus::Ok, None, Vec::new());
+        let xml = render(&[ok], &FailurePolicy::default()).unwrap();
+        assert!(xml.contains(r#"<testsuite name="links" tests="1" failures="0" skipped="0">"#));
+    }
+
+    #[test]
+    fn test_warnings_are_skipped() {
+        let policy = FailurePolicy {
+            fail_on: None,
+            warn_on: vec!["timeout".to_string()],
+            ..FailurePolicy::default()
+        };
+        let readme = vec![SourceLocation::new("README.md")];
+        let timeout = LinkCheckResult::new("https://slow.com", LinkStatus::Timeout, Some("Request timed out"), readme);
+        let xml = render(&[timeout], &policy).unwrap();
+        assert!(xml.contains(r#"failures="0" skipped="1">"#));
+        assert!(xml.contains(r#"<skipped type="timeout" message="Request timed out">"#));
+        assert!(!xml.contains("<failure"));
+    }
+}
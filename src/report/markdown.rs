@@ -0,0 +1,229 @@
+// src/report/markdown.rs
+// =============================================================================
+// This module renders results as GitHub-flavored Markdown.
+//
+// The output is meant to be pasted into a pull request comment or appended
+// to $GITHUB_STEP_SUMMARY:
+// - A summary table with the number of links per status
+// - A collapsible <details> section listing failing links (and --warn-on
+//   warnings, marked ⚠️), grouped by the file they were found in, with
+//   line numbers
+//
+// GitHub rejects comments over 65,536 characters, so the report is
+// truncated: once it gets close to MAX_REPORT_BYTES we stop listing links and
+// say how many were left out. The summary table is always complete.
+//
+// Rust concepts:
+// - std::fmt::Write: Lets writeln! write into a String
+// - Byte budgets: Checking String::len() as we go
+// =============================================================================
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+
+use super::group_by_source;
+use crate::checker::{FailurePolicy, LinkCheckResult, Outcome, SourceLocation};
+
+// Upper bound for the whole report, safely under GitHub's comment limit
+const MAX_REPORT_BYTES: usize = 60_000;
+
+// Renders results as a Markdown report
+pub fn render(results: &[LinkCheckResult], policy: &FailurePolicy) -> Result<String, fmt::Error> {
+    render_with_limit(results, policy, MAX_REPORT_BYTES)
+}
+
+// Renders results, keeping the report under `limit` bytes
+fn render_with_limit(
+    results: &[LinkCheckResult],
+    policy: &FailurePolicy,
+    limit: usize,
+) -> Result<String, fmt::Error> {
+    let mut out = String::new();
+
+    writeln!(out, "## 🔗 link-guardian report")?;
+    writeln!(out)?;
+
+    // Summary table: one row per status, sorted by name for stable output
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for result in results {
+        *counts.entry(result.status.name()).or_insert(0) += 1;
+    }
+    writeln!(out, "| Status | Links |")?;
+    writeln!(out, "|---|---:|")?;
+    for (name, count) in &counts {
+        writeln!(out, "| {} | {} |", name, count)?;
+    }
+    writeln!(out, "| **Total** | **{}** |", results.len())?;
+    writeln!(out)?;
+
+    // Failures and warnings are listed; passing links only appear in the counts
+    let listed: Vec<LinkCheckResult> = results
+        .iter()
+        .filter(|r| policy.outcome_of(r) != Outcome::Pass)
+        .cloned()
+        .collect();
+    if listed.is_empty() {
+        writeln!(out, "✅ All links are healthy!")?;
+        return Ok(out);
+    }
+
+    let failing = listed.iter().filter(|r| policy.outcome_of(r) == Outcome::Fail).count();
+    let warnings = listed.len() - failing;
+    let mut summary = format!("❌ {} failing link(s)", failing);
+    if warnings > 0 {
+        summary.push_str(&format!(", ⚠️ {} warning(s)", warnings));
+    }
+
+    writeln!(out, "<details open>")?;
+    writeln!(out, "<summary>{}</summary>", summary)?;
+    writeln!(out)?;
+
+    // Room needed to close the report properly (and explain truncation)
+    const FOOTER_RESERVE: usize = 200;
+
+    let groups = group_by_source(&listed, "(unknown source)");
+    let total_rows: usize = groups.iter().map(|(_, rows)| rows.len()).sum();
+    let mut written = 0;
+
+    'groups: for (source, rows) in &groups {
+        let mut section = String::new();
+        writeln!(section, "### `{}`", source)?;
+        writeln!(section)?;
+        writeln!(section, "| Line | URL | Status | Message |")?;
+        writeln!(section, "|---:|---|---|---|")?;
+
+        if out.len() + section.len() + FOOTER_RESERVE > limit {
+            break;
+        }
+        out.push_str(&section);
+
+        for (result, location) in rows {
+            let status = match policy.outcome_of(result) {
+                Outcome::Warn => format!("⚠️ {}", result.status.name()),
+                _ => result.status.name().to_string(),
+            };
+            let row = format!(
+                "| {} | {} | {} | {} |\n",
+                position_label(*location),
+                escape_cell(&result.url),
+                status,
+                escape_cell(result.message.as_deref().unwrap_or(""))
+            );
+            if out.len() + row.len() + FOOTER_RESERVE > limit {
+                writeln!(out)?;
+                break 'groups;
+            }
+            out.push_str(&row);
+            written += 1;
+        }
+        writeln!(out)?;
+    }
+
+    if written < total_rows {
+        writeln!(
+            out,
+            "_…and {} more failing occurrence(s) not shown to keep this report under the size limit._",
+            total_rows - written
+        )?;
+        writeln!(out)?;
+    }
+
+    writeln!(out, "</details>")?;
+    Ok(out)
+}
+
+// Shows where in the file a link is: "12", or "cell 3, line 2" in notebooks
+fn position_label(location: Option<&SourceLocation>) -> String {
+    match location {
+        Some(SourceLocation { cell: Some(cell), line: Some(line), .. }) => {
+            format!("cell {}, line {}", cell, line)
+        }
+        Some(SourceLocation { cell: Some(cell), .. }) => format!("cell {}", cell),
+        Some(SourceLocation { line: Some(line), .. }) => line.to_string(),
+        _ => String::new(),
+    }
+}
+
+// Makes text safe inside a Markdown table cell
+//
+// A "|" would start a new column and a newline would end the row.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. What is 'groups: before the for loop?
+//    - A loop label. `break 'groups;` exits the OUTER loop from inside
+//      the inner one - plain `break` would only leave the inner loop
+//
+// 2. Why BTreeMap instead of HashMap for the counts?
+//    - BTreeMap keeps keys sorted, so the summary rows always come out in
+//      the same order (HashMap order is random)
+//
+// 3. What does the pattern SourceLocation { cell: Some(cell), .. } do?
+//    - It matches only when `cell` is Some, and binds the number inside
+//    - `..` means "ignore the other fields"
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::LinkStatus;
+
+    const BROKEN: LinkStatus = LinkStatus::Broken { code: 404 };
+
+    fn on_line(line: usize) -> Vec<SourceLocation> {
+        vec![SourceLocation {
+            line: Some(line),
+            ..SourceLocation::new("README.md")
+        }]
+    }
+
+    #[test]
+    fn test_summary_and_details() {
+        let ok = LinkCheckResult::new("https://example.com", LinkStatus::Ok, None, Vec::new());
+        let broken = LinkCheckResult::new("https://example.com/missing", BROKEN, Some("HTTP 404"), on_line(7));
+        let markdown = render(&[ok, broken], &FailurePolicy::default()).unwrap();
+
+        assert!(markdown.contains("| broken | 1 |"));
+        assert!(markdown.contains("| **Total** | **2** |"));
+        assert!(markdown.contains("<summary>❌ 1 failing link(s)</summary>"));
+        assert!(markdown.contains("### `README.md`"));
+        assert!(markdown.contains("| 7 | https://example.com/missing | broken | HTTP 404 |"));
+    }
+
+    #[test]
+    fn test_all_healthy() {
+        assert!(render(&[], &FailurePolicy::default()).unwrap().contains("All links are healthy"));
+    }
+
+    #[test]
+    fn test_warnings_marked() {
+        let policy = FailurePolicy {
+            fail_on: None,
+            warn_on: vec!["broken".to_string()],
+            ..FailurePolicy::default()
+        };
+        let broken = LinkCheckResult::new("https://example.com/missing", BROKEN, Some("HTTP 404"), on_line(7));
+        let markdown = render(&[broken], &policy).unwrap();
+        assert!(markdown.contains("<summary>❌ 0 failing link(s), ⚠️ 1 warning(s)</summary>"));
+        assert!(markdown.contains("| 7 | https://example.com/missing | ⚠️ broken | HTTP 404 |"));
+    }
+
+    #[test]
+    fn test_truncation() {
+        let results: Vec<_> = (0..100)
+            .map(|i| {
+                let url = format!("https://example.com/page/{}", i);
+                LinkCheckResult::new(&url, BROKEN, Some("HTTP 404"), on_line(i + 1))
+            })
+            .collect();
+        let markdown = render_with_limit(&results, &FailurePolicy::default(), 2_000).unwrap();
+
+        assert!(markdown.len() <= 2_000);
+        assert!(markdown.contains("more failing occurrence(s) not shown"));
+        assert!(markdown.trim_end().ends_with("</details>"));
+    }
+}
@@ -0,0 +1,285 @@
+// src/report/mod.rs
+// =============================================================================
+// This module turns link check results into output people (and tools) read.
+//
+// Submodules:
+// - table: Human-readable table for the terminal (the default), sized and
+//   colored for the terminal it is printed to
+// - sarif: SARIF 2.1.0 for GitHub code scanning and other dashboards
+// - junit: JUnit XML for the test report UI of Jenkins, GitLab, etc.
+// - html: A self-contained HTML page for people who don't read JSON
+// - markdown: Markdown for PR comments and GitHub job summaries
+// - github: GitHub Actions annotations shown inline on pull requests
+// - csv: Comma-separated values for spreadsheets and scripts
+// - ndjson: Newline-delimited JSON, streamed while links are being checked
+// - diff: Compares two saved JSON reports (`link-guardian diff`)
+//
+// JSON output is simple enough to live right here, as is reading saved JSON
+// reports back in (`link-guardian report` and `diff`).
+//
+// Every format renders into a String first and is then printed to stdout or
+// written to the file given with --output. The exception is NDJSON during a
+// scan: NdjsonWriter writes each result as soon as it arrives.
+//
+// Rust concepts:
+// - Enums + match: One function picks the right renderer for each format
+// - Modules: Each output format lives in its own file
+// =============================================================================
+
+mod table;
+mod sarif;
+mod junit;
+mod html;
+mod markdown;
+mod github;
+mod csv;
+mod ndjson;
+mod diff;
+
+pub use diff::ReportDiff;
+pub use ndjson::NdjsonWriter;
+pub use table::TableOptions;
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::info;
+
+use crate::checker::{FailurePolicy, LinkCheckResult, SourceLocation};
+use crate::cli::{OutputArgs, OutputFormat};
+
+// Prints the results in the format chosen on the command line
+//
+// Parameters:
+//   results: slice of LinkCheckResult structs
+//   output: --format, --output and friends (see cli::OutputArgs)
+//   policy: --fail-on / --warn-on, so reports agree with the exit code
+pub fn print_results(results: &[LinkCheckResult], output: &OutputArgs, policy: &FailurePolicy) -> Result<()> {
+    let output_file = output.output_file.as_deref();
+    let table = TableOptions::detect(output_file.is_none(), output.failures_only);
+    let rendered = render(results, output.format(), &table, policy)?;
+    write_output(&rendered, output_file)
+}
+
+// Writes a rendered report to the file, or to stdout if there is none
+pub fn write_output(rendered: &str, output_file: Option<&Path>) -> Result<()> {
+    match output_file {
+        Some(path) => {
+            std::fs::write(path, rendered)
+                .with_context(|| format!("Could not write report to {}", path.display()))?;
+            info!("📝 Report written to {}", path.display());
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+// Reads a report saved with --format json (or --format ndjson)
+//
+// Returns the results it contains, ready to be compared or rendered again.
+pub fn load_json(path: &Path) -> Result<Vec<LinkCheckResult>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Could not read report {}", path.display()))?;
+    let not_a_report = || format!("{} is not a JSON report from link-guardian", path.display());
+
+    // A JSON array starts with '['; anything else is read as NDJSON
+    if text.trim_start().starts_with('[') {
+        serde_json::from_str(&text).with_context(not_a_report)
+    } else {
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).with_context(not_a_report))
+            .collect()
+    }
+}
+
+// Merges several reports into one, with each URL listed once
+//
+// Used for shards from parallel CI jobs: the same link may have been checked
+// by more than one job. Its locations are combined, and if the jobs disagree
+// the failing result wins so a broken link is never hidden. URLs keep the
+// order in which they were first seen.
+pub fn merge_reports(reports: Vec<Vec<LinkCheckResult>>) -> Vec<LinkCheckResult> {
+    let mut merged: Vec<LinkCheckResult> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for result in reports.into_iter().flatten() {
+        let position = match index.get(&result.url) {
+            Some(&position) => position,
+            None => {
+                index.insert(result.url.clone(), merged.len());
+                merged.push(result);
+                continue;
+            }
+        };
+
+        let existing = &mut merged[position];
+        let mut locations = std::mem::take(&mut existing.locations);
+        if existing.is_ok() && !result.is_ok() {
+            *existing = result.clone();
+        }
+        for location in result.locations {
+            if !locations.contains(&location) {
+                locations.push(location);
+            }
+        }
+        existing.locations = locations;
+    }
+
+    merged
+}
+
+// Renders the results into a String in the chosen format
+//
+// `table` only affects the table format. `policy` decides which results the
+// human-facing formats, CI annotations and SARIF levels show as failures or
+// warnings.
+pub fn render(
+    results: &[LinkCheckResult],
+    format: OutputFormat,
+    table: &TableOptions,
+    policy: &FailurePolicy,
+) -> Result<String> {
+    let output = match format {
+        OutputFormat::Table => table::render_with(results, table, policy)?,
+        OutputFormat::Json => {
+            // Serialize results to JSON
+            let mut json = serde_json::to_string_pretty(results)?;
+            json.push('\n');
+            json
+        }
+        OutputFormat::Sarif => sarif::render(results, policy)?,
+        OutputFormat::Junit => junit::render(results, policy)?,
+        OutputFormat::Html => html::render(results, policy)?,
+        OutputFormat::Markdown => markdown::render(results, policy)?,
+        OutputFormat::Github => github::render(results, policy)?,
+        OutputFormat::Csv => csv::render(results),
+        OutputFormat::Ndjson => ndjson::render(results)?,
+    };
+    Ok(output)
+}
+
+// A result at one of the places it was found
+type Occurrence<'a> = (&'a LinkCheckResult, Option<&'a SourceLocation>);
+
+// Groups results by the file or page they were found in
+//
+// A link found in several places appears once per place. Links without any
+// location are grouped under `unknown_source`. Groups keep the order in
+// which sources were first seen, so reports are stable between runs.
+fn group_by_source<'a>(
+    results: &'a [LinkCheckResult],
+    unknown_source: &str,
+) -> Vec<(String, Vec<Occurrence<'a>>)> {
+    let mut groups: Vec<(String, Vec<Occurrence<'a>>)> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+
+    for result in results {
+        let locations: Vec<Option<&SourceLocation>> = if result.locations.is_empty() {
+            vec![None]
+        } else {
+            result.locations.iter().map(Some).collect()
+        };
+
+        for location in locations {
+            let source = location.map(|l| l.source.as_str()).unwrap_or(unknown_source);
+            let position = *index.entry(source.to_string()).or_insert_with(|| {
+                groups.push((source.to_string(), Vec::new()));
+                groups.len() - 1
+            });
+            groups[position].1.push((result, location));
+        }
+    }
+
+    groups
+}
+
+// Escapes text for use inside XML/HTML content and attribute values
+//
+// Example: "a<b & \"c\"" -> "a&lt;b &amp; &quot;c&quot;"
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why render to a String instead of printing directly?
+//    - A String can be printed, written to a file, or checked in a test
+//    - Printing directly would tie every format to the terminal
+//
+// 2. What does the ? do inside the match?
+//    - Each renderer returns a Result; ? unwraps it or returns the error
+//    - anyhow converts the different error types for us
+//
+// 3. What does the 'a in Occurrence<'a> mean?
+//    - It's a lifetime: the references live as long as the results slice
+//    - Grouping borrows the results instead of copying every URL and message
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_markup() {
+        assert_eq!(escape_markup(r#"a<b & "c"'"#), "a&lt;b &amp; &quot;c&quot;&#39;");
+    }
+
+    #[test]
+    fn test_load_json_and_ndjson() {
+        let results = vec![LinkCheckResult {
+            url: "https://example.com/gone".to_string(),
+            status: crate::checker::LinkStatus::Broken { code: 404 },
+            message: Some("HTTP 404".to_string()),
+            locations: vec![SourceLocation::new("README.md")],
+        }];
+        let dir = std::env::temp_dir();
+        let json = dir.join(format!("link-guardian-report-{}.json", std::process::id()));
+        let ndjson = dir.join(format!("link-guardian-report-{}.ndjson", std::process::id()));
+        std::fs::write(&json, serde_json::to_string_pretty(&results).unwrap()).unwrap();
+        std::fs::write(&ndjson, ndjson::render(&results).unwrap()).unwrap();
+
+        for path in [json, ndjson] {
+            let loaded = load_json(&path).unwrap();
+            assert_eq!(loaded[0].status, results[0].status);
+            assert_eq!(loaded[0].locations[0].source, "README.md");
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_merge_reports() {
+        let result = |url: &str, status, source: &str| LinkCheckResult {
+            url: url.to_string(),
+            status,
+            message: None,
+            locations: vec![SourceLocation::new(source)],
+        };
+        let shard_one = vec![
+            result("https://a.com", crate::checker::LinkStatus::Ok, "one.md"),
+            result("https://b.com", crate::checker::LinkStatus::Ok, "one.md"),
+        ];
+        let shard_two = vec![
+            result("https://a.com", crate::checker::LinkStatus::Timeout, "two.md"),
+            result("https://a.com", crate::checker::LinkStatus::Ok, "one.md"),
+        ];
+
+        let merged = merge_reports(vec![shard_one, shard_two]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].status, crate::checker::LinkStatus::Timeout);
+        let sources: Vec<&str> = merged[0].locations.iter().map(|l| l.source.as_str()).collect();
+        assert_eq!(sources, ["one.md", "two.md"]);
+    }
+}
@@ -0,0 +1,145 @@
+// src/report/ndjson.rs
+// =============================================================================
+// This module writes results as NDJSON (newline-delimited JSON).
+//
+// Every line is one complete JSON object, the same shape as an element of
+// the --format json array:
+//
+//   {"url":"https://example.com","status":"ok"}
+//   {"url":"https://example.com/gone","status":"broken","code":404,"message":"HTTP 404 Not Found"}
+//
+// Unlike a JSON array, NDJSON can be read line by line while it is still
+// being written. During a scan, NdjsonWriter prints each result the moment
+// its check completes, so `link-guardian ... --format ndjson | jq` shows
+// progress on large sites instead of waiting for the very end.
+//
+// Rust concepts:
+// - Box<dyn Write>: One type for "stdout or a file"
+// - Remembering the first error: The callback can't return one, so we keep it
+// =============================================================================
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::checker::LinkCheckResult;
+
+// Renders all results at once (one line each)
+pub fn render(results: &[LinkCheckResult]) -> Result<String> {
+    let mut out = String::new();
+    for result in results {
+        out.push_str(&serde_json::to_string(result)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+// Writes results one line at a time, as they arrive
+pub struct NdjsonWriter {
+    out: Box<dyn Write>,
+    /// The file we write to, None for stdout
+    path: Option<PathBuf>,
+    /// The first write error, reported by finish()
+    error: Option<anyhow::Error>,
+}
+
+impl NdjsonWriter {
+    // Opens the destination: the given file, or stdout when there is none
+    pub fn create(output_file: Option<&Path>) -> Result<Self> {
+        let out: Box<dyn Write> = match output_file {
+            Some(path) => {
+                let file = File::create(path)
+                    .with_context(|| format!("Could not write report to {}", path.display()))?;
+                Box::new(BufWriter::new(file))
+            }
+            None => Box::new(io::stdout()),
+        };
+
+        Ok(NdjsonWriter {
+            out,
+            path: output_file.map(Path::to_path_buf),
+            error: None,
+        })
+    }
+
+    // Writes one result as a line and flushes it so readers see it right away
+    //
+    // Errors are remembered instead of returned, because this is called from
+    // inside the link checker; after the first error nothing more is written.
+    pub fn write(&mut self, result: &LinkCheckResult) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(e) = self.write_line(result) {
+            self.error = Some(e);
+        }
+    }
+
+    fn write_line(&mut self, result: &LinkCheckResult) -> Result<()> {
+        let line = serde_json::to_string(result)?;
+        writeln!(self.out, "{}", line)?;
+        self.out.flush()?;
+        Ok(())
+    }
+
+    // Finishes the stream, returning the first error that happened (if any)
+    pub fn finish(mut self) -> Result<()> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        self.out.flush()?;
+        if let Some(path) = &self.path {
+            info!("📝 Report written to {}", path.display());
+        }
+        Ok(())
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. What is Box<dyn Write>?
+//    - "Some type that implements Write, stored on the heap"
+//    - Stdout and BufWriter<File> are different types; boxing lets one
+//      field hold either
+//
+// 2. Why flush after every line?
+//    - Output is buffered, so without flushing a reader might not see the
+//      line until much later - which defeats the point of streaming
+//
+// 3. Why does finish() take `mut self` (not &mut self)?
+//    - It consumes the writer, so it can't accidentally be used afterwards
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::LinkStatus;
+
+    #[test]
+    fn test_one_object_per_line() {
+        let results = vec![
+            LinkCheckResult {
+                url: "https://example.com".to_string(),
+                status: LinkStatus::Ok,
+                message: None,
+                locations: Vec::new(),
+            },
+            LinkCheckResult {
+                url: "https://example.com/gone".to_string(),
+                status: LinkStatus::Broken { code: 404 },
+                message: Some("HTTP 404".to_string()),
+                locations: Vec::new(),
+            },
+        ];
+        let ndjson = render(&results).unwrap();
+        let lines: Vec<&str> = ndjson.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], r#"{"url":"https://example.com","status":"ok"}"#);
+        let second: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second["status"], "broken");
+    }
+}
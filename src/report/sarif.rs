@@ -0,0 +1,351 @@
+// src/report/sarif.rs
+// =============================================================================
+// This module renders results as SARIF 2.1.0.
+//
+// SARIF (Static Analysis Results Interchange Format) is the JSON format
+// GitHub code scanning and many other dashboards accept from linters.
+// https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+//
+// How we map our results:
+// - One rule per failure category (each LinkStatus except Ok)
+// - One SARIF result per place a failing link appears (file + line)
+// - Its level follows --fail-on / --warn-on: "error" for links that fail
+//   the run, "warning" for warnings, "note" for the rest
+// - A fingerprint per URL + location, so dashboards can track an alert
+//   across runs instead of opening a new one every time
+//
+// Rust concepts:
+// - serde_json::json!: Build JSON with a JSON-like syntax
+// - Constant tables: Static data describing our rules
+// =============================================================================
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+use crate::cache::fnv1a;
+use crate::checker::{FailurePolicy, LinkCheckResult, Outcome, SourceLocation};
+
+// Describes one SARIF rule (one kind of link failure)
+struct Rule {
+    /// Same as LinkStatus::name(), used as the SARIF ruleId
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+    /// SARIF level without --fail-on / --warn-on: "error", "warning" or "note"
+    level: &'static str,
+}
+
+const RULES: &[Rule] = &[
+    Rule {
+        id: "broken",
+        name: "BrokenLink",
+        description: "Link returns 404 Not Found or 410 Gone",
+        level: "error",
+    },
+    Rule {
+        id: "client_error",
+        name: "ClientErrorResponse",
+        description: "Link returns a 4xx status other than 401, 403, 404, 410 or 429",
+        level: "error",
+    },
+    Rule {
+        id: "unexpected_status",
+        name: "UnexpectedStatus",
+        description: "Link returns a status code outside the usual HTTP ranges",
+        level: "error",
+    },
+    Rule {
+        id: "invalid_url",
+        name: "InvalidUrl",
+        description: "The link is not a valid http or https URL",
+        level: "error",
+    },
+    Rule {
+        id: "connection_refused",
+        name: "ConnectionRefused",
+        description: "Nothing accepts connections at the link's host and port",
+        level: "error",
+    },
+    Rule {
+        id: "dns_error",
+        name: "UnresolvableHost",
+        description: "The link's hostname could not be resolved",
+        level: "error",
+    },
+    Rule {
+        id: "ssl_error",
+        name: "TlsError",
+        description: "The link's SSL/TLS certificate is invalid",
+        level: "error",
+    },
+    Rule {
+        id: "too_many_redirects",
+        name: "RedirectLoop",
+        description: "The link redirects too many times (likely a redirect loop)",
+        level: "error",
+    },
+    Rule {
+        id: "unauthorized",
+        name: "AuthenticationRequired",
+        description: "Link returns 401 Unauthorized",
+        level: "warning",
+    },
+    Rule {
+        id: "forbidden",
+        name: "AccessForbidden",
+        description: "Link returns 403 Forbidden",
+        level: "warning",
+    },
+    Rule {
+        id: "rate_limited",
+        name: "RateLimited",
+        description: "Link returns 429 Too Many Requests",
+        level: "warning",
+    },
+    Rule {
+        id: "server_error",
+        name: "ServerError",
+        description: "Link returns a 5xx server error",
+        level: "warning",
+    },
+    Rule {
+        id: "connection_reset",
+        name: "ConnectionReset",
+        description: "The server closed the connection before answering",
+        level: "warning",
+    },
+    Rule {
+        id: "timeout",
+        name: "LinkTimeout",
+        description: "The request timed out",
+        level: "warning",
+    },
+    Rule {
+        id: "error",
+        name: "LinkError",
+        description: "The request failed for another reason",
+        level: "warning",
+    },
+    Rule {
+        id: "redirect",
+        name: "RedirectedLink",
+        description: "The link redirects to another URL; consider linking to the target directly",
+        level: "note",
+    },
+];
+
+// Renders results as a SARIF 2.1.0 log
+//
+// Links with status Ok are not included - SARIF only lists problems.
+// `policy` decides the level of each result.
+pub fn render(results: &[LinkCheckResult], policy: &FailurePolicy) -> Result<String> {
+    let mut sarif_results = Vec::new();
+
+    for result in results {
+        let rule_index = match RULES.iter().position(|r| r.id == result.status.name()) {
+            Some(index) => index,
+            None => continue,  // Ok links have no rule
+        };
+        let rule = &RULES[rule_index];
+        let level = match policy.outcome_of(result) {
+            Outcome::Fail => "error",
+            Outcome::Warn => "warning",
+            Outcome::Pass => "note",
+        };
+
+        // One SARIF result per location, so each occurrence gets annotated
+        // Links without a known location still get one result
+        let locations: Vec<Option<&SourceLocation>> = if result.locations.is_empty() {
+            vec![None]
+        } else {
+            result.locations.iter().map(Some).collect()
+        };
+
+        for location in locations {
+            let mut entry = json!({
+                "ruleId": rule.id,
+                "ruleIndex": rule_index,
+                "level": level,
+                "message": { "text": message_text(result, location) },
+                "partialFingerprints": {
+                    "linkGuardian/v1": fingerprint(&result.url, location),
+                },
+            });
+            if let Some(location) = location {
+                entry["locations"] = json!([physical_location(location)]);
+            }
+            sarif_results.push(entry);
+        }
+    }
+
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.id,
+                "name": rule.name,
+                "shortDescription": { "text": rule.description },
+                "defaultConfiguration": { "level": rule.level },
+            })
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/Vswaroop04/link-guardian",
+                    "rules": rules,
+                }
+            },
+            "results": sarif_results,
+        }]
+    });
+
+    let mut output = serde_json::to_string_pretty(&log)?;
+    output.push('\n');
+    Ok(output)
+}
+
+// Builds the human-readable message for one result
+//
+// Example: "https://example.com/missing is broken: HTTP 404"
+fn message_text(result: &LinkCheckResult, location: Option<&SourceLocation>) -> String {
+    let mut text = format!("{} is {}", result.url, result.status.name().replace('_', " "));
+    if let Some(message) = &result.message {
+        text.push_str(&format!(": {}", message));
+    }
+
+    // Notebook line numbers are relative to a cell, not to the JSON file,
+    // so they go into the message instead of the region
+    if let Some(location) = location.filter(|l| l.cell.is_some()) {
+        text.push_str(&format!(" (at {})", location));
+    }
+    text
+}
+
+// Converts one of our locations into a SARIF physicalLocation
+fn physical_location(location: &SourceLocation) -> Value {
+    let mut region = Map::new();
+    if location.cell.is_none() {
+        if let Some(line) = location.line {
+            region.insert("startLine".to_string(), json!(line));
+        }
+        if let Some(column) = location.column {
+            region.insert("startColumn".to_string(), json!(column));
+        }
+    }
+
+    let mut physical = json!({
+        "artifactLocation": { "uri": location.source },
+    });
+    if !region.is_empty() {
+        physical["region"] = Value::Object(region);
+    }
+
+    json!({ "physicalLocation": physical })
+}
+
+// A stable fingerprint for a URL at a location
+//
+// We use the FNV-1a hash because it is tiny and gives the same answer on
+// every platform and Rust version (std's HashMap hasher does not promise that).
+fn fingerprint(url: &str, location: Option<&SourceLocation>) -> String {
+    let key = match location {
+        Some(location) => format!("{}|{}", url, location),
+        None => url.to_string(),
+    };
+    format!("{:016x}", fnv1a(&key))
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. What does env!("CARGO_PKG_VERSION") do?
+//    - Reads a value at COMPILE time; Cargo sets it from Cargo.toml
+//    - The version in the report always matches the binary
+//
+// 2. Why index into a Value with entry["locations"] = ...?
+//    - serde_json::Value supports [] for reading and writing object fields
+//    - It's handy for adding optional fields after building the object
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::LinkStatus;
+
+    fn broken_result() -> LinkCheckResult {
+        let location = SourceLocation {
+            line: Some(12),
+            column: Some(5),
+            ..SourceLocation::new("README.md")
+        };
+        let broken = LinkStatus::Broken { code: 404 };
+        LinkCheckResult::new("https://example.com/missing", broken, Some("HTTP 404"), vec![location])
+    }
+
+    #[test]
+    fn test_sarif_structure() {
+        let ok = LinkCheckResult::new("https://example.com", LinkStatus::Ok, None, Vec::new());
+        let output = render(&[ok, broken_result()], &FailurePolicy::default()).unwrap();
+        let log: Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(log["version"], "2.1.0");
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ruleId"], "broken");
+        assert_eq!(results[0]["level"], "error");
+
+        let physical = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uri"], "README.md");
+        assert_eq!(physical["region"]["startLine"], 12);
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        let first = render(&[broken_result()], &FailurePolicy::default()).unwrap();
+        let second = render(&[broken_result()], &FailurePolicy::default()).unwrap();
+        assert_eq!(first, second);
+
+        let location = SourceLocation::new("docs/guide.md");
+        assert_ne!(
+            fingerprint("https://example.com/missing", Some(&location)),
+            fingerprint("https://example.com/missing", None)
+        );
+    }
+
+    #[test]
+    fn test_levels_follow_policy() {
+        let timeout = LinkCheckResult::new("https://slow.com", LinkStatus::Timeout, Some("Request timed out"), Vec::new());
+        let policy = FailurePolicy {
+            fail_on: Some(vec!["timeout".to_string()]),
+            warn_on: vec!["broken".to_string()],
+            ..FailurePolicy::default()
+        };
+        let output = render(&[broken_result(), timeout], &policy).unwrap();
+        let log: Value = serde_json::from_str(&output).unwrap();
+        let results = &log["runs"][0]["results"];
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[1]["level"], "error");
+
+        let policy = FailurePolicy {
+            fail_on: Some(vec!["timeout".to_string()]),
+            warn_on: Vec::new(),
+            ..FailurePolicy::default()
+        };
+        let output = render(&[broken_result()], &policy).unwrap();
+        assert!(output.contains("\"level\": \"note\""));
+    }
+
+    #[test]
+    fn test_every_failure_has_a_rule() {
+        for name in LinkStatus::NAMES.iter().filter(|n| **n != "ok") {
+            assert!(RULES.iter().any(|r| r.id == *name), "no SARIF rule for {}", name);
+        }
+    }
+}
@@ -0,0 +1,350 @@
+// src/report/table.rs
+// =============================================================================
+// This module renders results as a human-readable table for the terminal.
+//
+// The table adapts to where it is printed:
+// - In a terminal, columns are sized to fit the window and long values are
+//   shortened with "..."; statuses are colored (unless NO_COLOR is set)
+// - Piped or written to a file, nothing is truncated or colored, so the
+//   full URLs survive
+//
+// Columns: URL, STATUS, FOUND IN (where the link was found) and MESSAGE.
+// With --failures-only, passing links are left out of the table (they are
+// still counted in the summary). What passes, warns or fails follows
+// --fail-on / --warn-on, like the exit code.
+//
+// Rust concepts:
+// - std::fmt::Write: Lets writeln! write into a String
+// - chars(): Working with characters instead of bytes, so multi-byte UTF-8
+//   text is never cut in half
+// - std::io::IsTerminal: Checking whether stdout is a terminal
+// =============================================================================
+
+use std::fmt::{self, Write};
+use std::io::IsTerminal;
+
+use crate::checker::{FailurePolicy, LinkCheckResult, LinkStatus, Outcome};
+
+// How the table should be drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableOptions {
+    /// Maximum line width in characters, None = never truncate
+    pub width: Option<usize>,
+    /// Color statuses with ANSI escape codes
+    pub color: bool,
+    /// Only list links that are not OK
+    pub failures_only: bool,
+}
+
+impl TableOptions {
+    // Plain table: no colors, no truncation, every link listed
+    pub fn plain() -> Self {
+        TableOptions {
+            width: None,
+            color: false,
+            failures_only: false,
+        }
+    }
+
+    // Options for printing to stdout, based on what stdout is
+    //
+    // Parameters:
+    //   to_stdout: false when the table is written to a file (--output)
+    //   failures_only: only list failing links
+    pub fn detect(to_stdout: bool, failures_only: bool) -> Self {
+        let is_terminal = to_stdout && std::io::stdout().is_terminal();
+        TableOptions {
+            width: if is_terminal { terminal_width() } else { None },
+            color: is_terminal && std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()),
+            failures_only,
+        }
+    }
+}
+
+// The terminal width, from $COLUMNS or by asking the terminal
+fn terminal_width() -> Option<usize> {
+    if let Some(columns) = std::env::var("COLUMNS").ok().and_then(|c| c.parse().ok()) {
+        return Some(columns);
+    }
+    terminal_size::terminal_size().map(|(width, _)| width.0 as usize)
+}
+
+// Narrowest table we draw; smaller terminals just wrap
+const MIN_WIDTH: usize = 80;
+
+// Width of the STATUS column: a 2-column icon, a space and the longest
+// label ("TOO MANY REDIRECTS", "UNAUTHORIZED (401)")
+const STATUS_WIDTH: usize = 21;
+const LABEL_WIDTH: usize = 18;
+
+// ANSI escape codes for colors
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+// Renders a plain table (used inside other reports, e.g. GitHub annotations)
+pub fn render(results: &[LinkCheckResult], policy: &FailurePolicy) -> Result<String, fmt::Error> {
+    render_with(results, &TableOptions::plain(), policy)
+}
+
+// Renders results as a table followed by a summary
+pub fn render_with(
+    results: &[LinkCheckResult],
+    options: &TableOptions,
+    policy: &FailurePolicy,
+) -> Result<String, fmt::Error> {
+    let mut out = String::new();
+    let count = |outcome| results.iter().filter(|r| policy.outcome_of(r) == outcome).count();
+
+    let rows: Vec<&LinkCheckResult> = results
+        .iter()
+        .filter(|r| !options.failures_only || policy.outcome_of(r) != Outcome::Pass)
+        .collect();
+    let found_in: Vec<String> = rows.iter().map(|r| format_locations(r)).collect();
+
+    // Work out how wide the URL, FOUND IN and MESSAGE columns can be
+    let natural = [
+        column_width("URL", rows.iter().map(|r| r.url.as_str())),
+        column_width("FOUND IN", found_in.iter().map(String::as_str)),
+        column_width("MESSAGE", rows.iter().map(|r| r.message.as_deref().unwrap_or(""))),
+    ];
+    let [url_width, found_width, message_width] = match options.width {
+        // 3 spaces between the 4 columns
+        Some(width) => fit_columns(natural, width.max(MIN_WIDTH) - STATUS_WIDTH - 3),
+        None => natural,
+    };
+
+    // Table header
+    writeln!(
+        out,
+        "{:<url_width$} {:<STATUS_WIDTH$} {:<found_width$} MESSAGE",
+        "URL", "STATUS", "FOUND IN"
+    )?;
+    writeln!(out, "{}", "=".repeat(url_width + STATUS_WIDTH + found_width + message_width + 3))?;
+
+    // One row per result
+    for (result, found) in rows.iter().zip(&found_in) {
+        let message = result.message.as_deref().unwrap_or("");
+        writeln!(
+            out,
+            "{:<url_width$} {} {:<found_width$} {}",
+            truncate(&result.url, url_width),
+            format_status(&result.status, options.color),
+            truncate(found, found_width),
+            truncate(message, message_width),
+        )?;
+    }
+
+    writeln!(out)?;
+
+    // Summary
+    let ok_count = count(Outcome::Pass);
+    let warning_count = count(Outcome::Warn);
+    let broken_count = count(Outcome::Fail);
+
+    if options.failures_only && ok_count > 0 {
+        writeln!(out, "({} passing link(s) not shown)", ok_count)?;
+        writeln!(out)?;
+    }
+
+    writeln!(out, "📊 Summary:")?;
+    writeln!(out, "   ✅ OK: {}", ok_count)?;
+    if warning_count > 0 {
+        writeln!(out, "   ⚠️  Warnings: {}", warning_count)?;
+    }
+    writeln!(out, "   ❌ Broken: {}", broken_count)?;
+    writeln!(out, "   📋 Total: {}", results.len())?;
+
+    Ok(out)
+}
+
+// The widest value in a column (in characters), including its header
+fn column_width<'a>(header: &str, values: impl Iterator<Item = &'a str>) -> usize {
+    values
+        .map(|v| v.chars().count())
+        .chain(std::iter::once(header.len()))
+        .max()
+        .unwrap_or(0)
+}
+
+// Shrinks column widths so together they fit in `available` characters
+//
+// Narrow columns keep their natural width; the space left is shared equally
+// among the wide ones. Every column gets at least 8 characters.
+//
+// Example: natural [100, 10, 50] in 80 -> [35, 10, 35]
+fn fit_columns(natural: [usize; 3], available: usize) -> [usize; 3] {
+    let mut widths = natural;
+    let mut order = [0, 1, 2];
+    order.sort_by_key(|&i| natural[i]);
+
+    let mut remaining = available;
+    for (n, &i) in order.iter().enumerate() {
+        let share = remaining / (3 - n);
+        widths[i] = natural[i].min(share).max(8);
+        remaining = remaining.saturating_sub(widths[i]);
+    }
+    widths
+}
+
+// Shortens text to at most `max` characters, ending with "..." if cut
+//
+// Counts characters, not bytes, so a URL like "https://example.com/café"
+// is never split in the middle of "é".
+fn truncate(text: &str, max: usize) -> String {
+    // Tabs and newlines in messages would break the layout
+    let text = text.replace(['\n', '\t'], " ");
+    if text.chars().count() <= max {
+        return text;
+    }
+    let kept: String = text.chars().take(max.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
+
+// Where a link was found: the first location, plus how many others
+//
+// Example: "README.md:12:5 (+2 more)"
+fn format_locations(result: &LinkCheckResult) -> String {
+    match result.locations.split_first() {
+        None => String::new(),
+        Some((first, [])) => first.to_string(),
+        Some((first, rest)) => format!("{} (+{} more)", first, rest.len()),
+    }
+}
+
+// Formats the status as an icon and a label, padded to STATUS_WIDTH
+//
+// Statuses from an HTTP response show their code, e.g. "FORBIDDEN (403)".
+fn format_status(status: &LinkStatus, color: bool) -> String {
+    // Some emoji render narrower than others, hence the extra spaces
+    let (icon, label, ansi) = match status {
+        LinkStatus::Ok => ("✅", "OK", GREEN),
+        LinkStatus::Redirect { .. } => ("🔀", "REDIRECT", YELLOW),
+        LinkStatus::Broken { .. } => ("❌", "BROKEN", RED),
+        LinkStatus::Unauthorized { .. } => ("🔑", "UNAUTHORIZED", YELLOW),
+        LinkStatus::Forbidden { .. } => ("🚫", "FORBIDDEN", YELLOW),
+        LinkStatus::RateLimited { .. } => ("🐢", "RATE LIMITED", YELLOW),
+        LinkStatus::ClientError { .. } => ("❌", "CLIENT ERROR", RED),
+        LinkStatus::ServerError { .. } => ("💥", "SERVER ERROR", YELLOW),
+        LinkStatus::UnexpectedStatus { .. } => ("❓", "UNEXPECTED", RED),
+        LinkStatus::Timeout => ("⏱️ ", "TIMEOUT", YELLOW),
+        LinkStatus::SslError => ("🔒", "SSL ERROR", RED),
+        LinkStatus::TooManyRedirects => ("🔁", "TOO MANY REDIRECTS", RED),
+        LinkStatus::DnsError => ("🌐", "DNS ERROR", RED),
+        LinkStatus::ConnectionRefused => ("🔌", "CONN. REFUSED", RED),
+        LinkStatus::ConnectionReset => ("🔌", "CONN. RESET", YELLOW),
+        LinkStatus::InvalidUrl => ("🔗", "INVALID URL", RED),
+        LinkStatus::Error => ("⚠️ ", "ERROR", RED),
+    };
+    let label = match status.code() {
+        Some(code) => format!("{} ({})", label, code),
+        None => label.to_string(),
+    };
+
+    // Pad before coloring: escape codes take no space on screen but would
+    // count towards the padding
+    let label = format!("{:<LABEL_WIDTH$}", label);
+    if color {
+        format!("{} {}{}{}", icon, ansi, label, RESET)
+    } else {
+        format!("{} {}", icon, label)
+    }
+}
+
+// -----------------------------------------------------------------------------
+// BEGINNER NOTES:
+//
+// 1. Why not &url[..57] to shorten a URL?
+//    - String slices use BYTE positions
+//    - Characters like "é" or "日" take 2-4 bytes; slicing through the
+//      middle of one panics at runtime
+//    - chars().take(n) counts whole characters instead
+//
+// 2. What is {:<url_width$}?
+//    - Left-align and pad to the width stored in the variable url_width
+//    - The $ means "take the width from this named argument"
+//
+// 3. What are "\x1b[31m" and friends?
+//    - ANSI escape codes: invisible characters that tell the terminal to
+//      switch color; "\x1b[0m" switches back
+//    - We only send them to a terminal, and never when NO_COLOR is set
+//      (see https://no-color.org)
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::SourceLocation;
+
+    #[test]
+    fn test_truncate_multibyte() {
+        let url = "https://example.com/日本語のページ";
+        assert_eq!(truncate(url, 100), url);
+        assert_eq!(truncate(url, 25), "https://example.com/日本...");
+    }
+
+    #[test]
+    fn test_fit_columns() {
+        assert_eq!(fit_columns([100, 10, 50], 80), [35, 10, 35]);
+        assert_eq!(fit_columns([20, 10, 30], 80), [20, 10, 30]);
+    }
+
+    #[test]
+    fn test_failures_only_and_locations() {
+        let locations = vec![SourceLocation::new("README.md"), SourceLocation::new("docs/a.md")];
+        let broken = LinkStatus::Broken { code: 404 };
+        let results = vec![
+            LinkCheckResult::new("https://example.com/ok", LinkStatus::Ok, Some("HTTP 200"), locations.clone()),
+            LinkCheckResult::new("https://example.com/gone", broken, Some("HTTP 404"), locations),
+        ];
+        let options = TableOptions {
+            failures_only: true,
+            ..TableOptions::plain()
+        };
+        let table = render_with(&results, &options, &FailurePolicy::default()).unwrap();
+
+        assert!(!table.contains("https://example.com/ok"));
+        assert!(table.contains("README.md (+1 more)"));
+        assert!(table.contains("(1 passing link(s) not shown)"));
+        assert!(!table.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_summary_follows_policy() {
+        let broken = LinkStatus::Broken { code: 404 };
+        let results = vec![
+            LinkCheckResult::new("https://example.com/slow", LinkStatus::Timeout, None, Vec::new()),
+            LinkCheckResult::new("https://example.com/gone", broken, Some("HTTP 404"), Vec::new()),
+        ];
+        let policy = FailurePolicy {
+            fail_on: None,
+            warn_on: vec!["timeout".to_string()],
+            ..FailurePolicy::default()
+        };
+        let table = render_with(&results, &TableOptions::plain(), &policy).unwrap();
+
+        assert!(table.contains("   ⚠️  Warnings: 1\n"));
+        assert!(table.contains("   ❌ Broken: 1\n"));
+    }
+
+    #[test]
+    fn test_status_label_shows_code() {
+        assert_eq!(format_status(&LinkStatus::Broken { code: 410 }, false).trim_end(), "❌ BROKEN (410)");
+        assert_eq!(format_status(&LinkStatus::ServerError { code: 503 }, false).trim_end(), "💥 SERVER ERROR (503)");
+        assert_eq!(format_status(&LinkStatus::ConnectionRefused, false).trim_end(), "🔌 CONN. REFUSED");
+    }
+
+    #[test]
+    fn test_width_limit() {
+        let long = format!("https://example.com/{}", "a".repeat(200));
+        let options = TableOptions {
+            width: Some(100),
+            ..TableOptions::plain()
+        };
+        let result = LinkCheckResult::new(&long, LinkStatus::Broken { code: 404 }, Some("HTTP 404"), Vec::new());
+        let table = render_with(&[result], &options, &FailurePolicy::default()).unwrap();
+        let widest = table.lines().map(|l| l.chars().count()).max().unwrap();
+        assert!(widest <= 100, "line is {} characters wide", widest);
+    }
+}